@@ -0,0 +1,101 @@
+use cheese_game::{
+    Biome, Difficulty, DifficultyCurve, ObstacleRegistry, TerrainExport, TerrainNoise, Vertex,
+};
+
+pub const USAGE: &str = "\
+usage: cheese_rolling_forever export --seed <seed> --from <x,z> --to <x,z> --png <path>
+                                     [--obstacle-seed <seed>] [--biome <biome>] [--difficulty <difficulty>]
+                                     [--obj <path>]";
+
+#[derive(Default)]
+struct ExportArgs {
+    seed: Option<u32>,
+    obstacle_seed: Option<u32>,
+    biome: Option<Biome>,
+    difficulty: Option<Difficulty>,
+    from: Option<Vertex>,
+    to: Option<Vertex>,
+    png: Option<String>,
+    obj: Option<String>,
+}
+
+fn parse_seed(value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid seed: {}", value))
+}
+
+fn parse_biome(value: &str) -> Result<Biome, String> {
+    Biome::ALL
+        .into_iter()
+        .find(|biome| biome.label().eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("unknown biome: {}", value))
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    Difficulty::ALL
+        .into_iter()
+        .find(|difficulty| difficulty.label().eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("unknown difficulty: {}", value))
+}
+
+fn parse_chunk(value: &str) -> Result<Vertex, String> {
+    let (x, z) = value
+        .split_once(',')
+        .ok_or_else(|| format!("invalid chunk, expected x,z: {}", value))?;
+    match (x.trim().parse(), z.trim().parse()) {
+        (Ok(x), Ok(z)) => Ok(Vertex::new(x, z)),
+        _ => Err(format!("invalid chunk, expected x,z: {}", value)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<ExportArgs, String> {
+    let mut export_args = ExportArgs::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--seed" => export_args.seed = Some(parse_seed(value)?),
+            "--obstacle-seed" => export_args.obstacle_seed = Some(parse_seed(value)?),
+            "--biome" => export_args.biome = Some(parse_biome(value)?),
+            "--difficulty" => export_args.difficulty = Some(parse_difficulty(value)?),
+            "--from" => export_args.from = Some(parse_chunk(value)?),
+            "--to" => export_args.to = Some(parse_chunk(value)?),
+            "--png" => export_args.png = Some(value.clone()),
+            "--obj" => export_args.obj = Some(value.clone()),
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+    Ok(export_args)
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = parse_args(args)?;
+    let seed = args.seed.ok_or("missing --seed")?;
+    let from = args.from.ok_or("missing --from")?;
+    let to = args.to.ok_or("missing --to")?;
+    if args.png.is_none() && args.obj.is_none() {
+        return Err("nothing to export, pass --png and/or --obj".to_string());
+    }
+
+    let export = TerrainExport::new(from, to)
+        .with_registry(ObstacleRegistry::for_biome(args.biome.unwrap_or_default()))
+        .with_difficulty(DifficultyCurve::preset(args.difficulty.unwrap_or_default()));
+    let terrain_noise = TerrainNoise::new(seed);
+
+    if let Some(path) = args.png {
+        let (min, max) = export
+            .write_heightmap_png(&terrain_noise, &path)
+            .map_err(|error| error.to_string())?;
+        println!("Wrote {} (heights {} to {})", path, min, max);
+    }
+    if let Some(path) = args.obj {
+        export
+            .write_obj(&terrain_noise, args.obstacle_seed, &path)
+            .map_err(|error| error.to_string())?;
+        println!("Wrote {}", path);
+    }
+    Ok(())
+}
@@ -3,7 +3,17 @@ extern crate cfg_if;
 
 cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
+        mod export;
+
         fn main() {
+            let args = std::env::args().skip(1).collect::<Vec<_>>();
+            if args.first().is_some_and(|command| command == "export") {
+                if let Err(error) = export::run(&args[1..]) {
+                    eprintln!("{}\n\n{}", error, export::USAGE);
+                    std::process::exit(1);
+                }
+                return;
+            }
             cheese_game::run_app(None);
         }
     }
@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+use bevy::{asset::AssetPlugin, prelude::*};
+
+use bevy_xpbd_3d::prelude::*;
+
+use cheese_game::{
+    Person, PooledRagdoll, RagdollAssets, RagdollLimbs, RagdollPool, MAX_JUGGLE_COUNT,
+};
+
+// how many ragdolls leave and rejoin the race each frame
+const CHURN: usize = 5;
+const WARMUP_FRAMES: usize = 30;
+const FRAMES: usize = 300;
+
+// The lakitu as it used to be, spawning new ragdolls and despawning old ones, against the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Resource)]
+enum Lakitu {
+    Spawning,
+    Pooled,
+}
+
+fn main() {
+    for lakitu in [Lakitu::Spawning, Lakitu::Pooled] {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+            PhysicsPlugins::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_resource::<RagdollAssets>()
+        .init_resource::<RagdollPool>()
+        .insert_resource(lakitu)
+        .add_systems(Startup, spawn_ragdolls)
+        .add_systems(Update, churn_ragdolls);
+
+        for _ in 0..WARMUP_FRAMES {
+            app.update();
+        }
+        let mut total = Duration::ZERO;
+        for _ in 0..FRAMES {
+            let start = Instant::now();
+            app.update();
+            total += start.elapsed();
+        }
+
+        let world = &app.world;
+        println!(
+            "{:?}: {} ragdolls, {:.3}ms per frame, {} meshes, {} materials",
+            lakitu,
+            MAX_JUGGLE_COUNT,
+            total.as_secs_f64() * 1000. / FRAMES as f64,
+            world.resource::<Assets<Mesh>>().len(),
+            world.resource::<Assets<StandardMaterial>>().len(),
+        );
+    }
+}
+
+fn drop_point(index: usize) -> Vec3 {
+    Vec3::new((index % 10) as f32 * 4., 50., (index / 10) as f32 * 4.)
+}
+
+fn spawn_ragdolls(
+    mut commands: Commands,
+    lakitu: Res<Lakitu>,
+    mut pool: ResMut<RagdollPool>,
+    mut assets: ResMut<RagdollAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut rng = rand::thread_rng();
+    match *lakitu {
+        Lakitu::Spawning => {
+            for index in 0..MAX_JUGGLE_COUNT {
+                Person::random(&mut rng).spawn_ragdoll(
+                    drop_point(index),
+                    Vec3::ZERO,
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                );
+            }
+        }
+        Lakitu::Pooled => {
+            // more than are active, so that there are always some to swap in
+            for _ in 0..MAX_JUGGLE_COUNT + CHURN {
+                pool.build(
+                    Person::random(&mut rng),
+                    &mut assets,
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                );
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn churn_ragdolls(
+    mut commands: Commands,
+    lakitu: Res<Lakitu>,
+    ragdoll_query: Query<(Entity, &Person, &RagdollLimbs)>,
+    pooled_query: Query<(&PooledRagdoll, &RagdollLimbs)>,
+    mut pool: ResMut<RagdollPool>,
+    mut assets: ResMut<RagdollAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut frame: Local<usize>,
+) {
+    *frame += 1;
+    let mut rng = rand::thread_rng();
+    match *lakitu {
+        Lakitu::Spawning => {
+            for (index, (entity, _, _)) in ragdoll_query.iter().take(CHURN).enumerate() {
+                commands.entity(entity).despawn_recursive();
+                Person::random(&mut rng).spawn_ragdoll(
+                    drop_point(*frame * CHURN + index),
+                    Vec3::ZERO,
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                );
+            }
+        }
+        Lakitu::Pooled => {
+            // fill the race from the pool on the first frame, then swap a few each frame
+            let active = ragdoll_query.iter().count();
+            let to_drop = if active == 0 { MAX_JUGGLE_COUNT } else { CHURN };
+            for index in 0..to_drop {
+                let Some(entity) = pool.take(&mut rng) else {
+                    break;
+                };
+                let Ok((pooled, limbs)) = pooled_query.get(entity) else {
+                    continue;
+                };
+                RagdollPool::activate(
+                    entity,
+                    pooled.0,
+                    limbs,
+                    drop_point(*frame * CHURN + index),
+                    Vec3::ZERO,
+                    &mut commands,
+                );
+            }
+            // parked after the drops, so that they aren't picked straight back up
+            if active > 0 {
+                for (entity, person, limbs) in ragdoll_query.iter().take(CHURN) {
+                    pool.park(entity, *person, limbs, &mut assets, &mut commands);
+                }
+            }
+        }
+    }
+}
@@ -5,8 +5,8 @@ use bevy_geppetto::Test;
 use bevy_xpbd_3d::plugins::PhysicsDebugPlugin;
 
 use cheese_game::{
-    AppState, Cheese, CheeseRacePlugin, Level, PlayerCameraPlugin, SceneAssets, SceneAssetsPlugin,
-    TerrainNoise, TerrainPlugin,
+    AppState, Cheese, CheeseKind, CheeseRacePlugin, Level, PlayerCameraPlugin, SceneAssets,
+    SceneAssetsPlugin, TerrainNoise, TerrainPlugin,
 };
 
 fn main() {
@@ -34,6 +34,11 @@ fn handle_start(mut commands: Commands, scenes: Res<SceneAssets>) {
         ..Default::default()
     });
 
-    commands.spawn(Cheese::bundle(Cheese::default_transform(), &scenes));
+    Cheese::spawn(
+        CheeseKind::default(),
+        Cheese::default_transform(),
+        &scenes,
+        &mut commands,
+    );
     commands.spawn((Level::default(), Level::name()));
 }
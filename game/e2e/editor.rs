@@ -0,0 +1,26 @@
+use bevy_geppetto::Test;
+
+use bevy_xpbd_3d::plugins::PhysicsDebugPlugin;
+
+use cheese_game::{
+    AppState, CheeseRacePlugin, CheeseUIPlugin, EditorPlugin, ObstaclesPlugin, PlayerCameraPlugin,
+    RaceScenePlugin, SceneAssetsPlugin, TerrainPlugin,
+};
+
+fn main() {
+    Test::new("Course editor".to_string(), |app| {
+        app.add_plugins((
+            // the race plugins are needed to play-test the course
+            PlayerCameraPlugin,
+            CheeseRacePlugin,
+            TerrainPlugin,
+            ObstaclesPlugin,
+            RaceScenePlugin,
+            CheeseUIPlugin,
+            PhysicsDebugPlugin::default(),
+            SceneAssetsPlugin::new(AppState::Editor),
+            EditorPlugin,
+        ));
+    })
+    .run();
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use cheese_game::{
+    Biome, Chunk, Difficulty, DifficultyCurve, DifficultySettings, LanePlanner, Level, Obstacle,
+    ObstacleNoise, ObstacleRegistry, Obstacles, Vertex,
+};
+
+const SEEDS: [u32; 5] = [0, 1, 1234, 54321, u32::MAX];
+
+// far enough down the hill that every preset has reached its densest keyframe
+const FIRST_ROW: i32 = 60;
+
+// every row of every chunk keeps a lane open once its neighbours' obstacles are placed too
+fn main() {
+    let level = Level::default();
+    let chunk = |x: i32, z: i32| Chunk::new(Vertex::new(x, z), level.chunk_size, level.quad_size);
+    let curves = Difficulty::ALL
+        .into_iter()
+        .map(DifficultyCurve::preset)
+        // an obstacle at every spot the placement considers, most of them moving
+        .chain(std::iter::once(DifficultyCurve::new(vec![(
+            0.,
+            DifficultySettings {
+                obstacle_threshold: -1.,
+                moving_obstacle_weight: 4.,
+                ..Default::default()
+            },
+        )])))
+        .collect::<Vec<_>>();
+
+    for ((seed, biome), curve) in SEEDS
+        .into_iter()
+        .cartesian_product(Biome::ALL)
+        .cartesian_product(curves.iter())
+    {
+        let noise = ObstacleNoise::new(seed);
+        let registry = ObstacleRegistry::for_biome(biome);
+        let placed = (-3..=3)
+            .cartesian_product(FIRST_ROW..FIRST_ROW + 8)
+            .map(|(x, z)| {
+                let obstacles = Obstacles::generate_obstacles_for_chunk(
+                    chunk(x, z),
+                    seed,
+                    &noise.get(),
+                    &registry,
+                    curve,
+                )
+                .collect::<Vec<Obstacle>>();
+                ((x, z), obstacles)
+            })
+            .collect::<HashMap<_, _>>();
+
+        // the outer ring of chunks is only there to surround the ones that are checked
+        for (x, z) in (-2..=2).cartesian_product(FIRST_ROW + 1..FIRST_ROW + 7) {
+            let nearby = (x - 1..=x + 1)
+                .cartesian_product(z - 1..=z + 1)
+                .flat_map(|key| placed[&key].iter().cloned());
+            let narrowest = LanePlanner::new(&chunk(x, z), nearby).narrowest_lane();
+            assert!(
+                narrowest >= LanePlanner::MIN_LANE_WIDTH,
+                "seed {} in {:?} with {:?}: chunk ({}, {}) only has a {} wide lane",
+                seed,
+                biome,
+                curve.preset,
+                x,
+                z,
+                narrowest,
+            );
+        }
+    }
+    println!("Every row keeps a lane open");
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use bevy_geppetto::Test;
+
+use bevy_xpbd_3d::plugins::PhysicsDebugPlugin;
+
+use cheese_game::{
+    AppState, CheeseRacePlugin, Level, Obstacle, ObstacleKind, ObstaclesPlugin, RaceScenePlugin,
+    SceneAssetsPlugin, TerrainNoise, TerrainPlugin, TextureAssets, Vertex,
+};
+
+const KINDS: [ObstacleKind; 12] = [
+    ObstacleKind::Wall { width: 6 },
+    ObstacleKind::Fence { width: 12 },
+    ObstacleKind::Post,
+    ObstacleKind::Pillar,
+    ObstacleKind::HayBale,
+    ObstacleKind::Boulder,
+    ObstacleKind::Stump,
+    ObstacleKind::RollingLog,
+    ObstacleKind::SwingingGate,
+    ObstacleKind::SlidingBarrier,
+    ObstacleKind::BarrelDrop,
+    ObstacleKind::Tractor,
+];
+
+fn main() {
+    Test::new("Obstacle kinds".to_string(), |app| {
+        app.add_plugins((
+            CheeseRacePlugin,
+            RaceScenePlugin,
+            TerrainPlugin,
+            // for the scripted motion, alongside the generated obstacles
+            ObstaclesPlugin,
+            PhysicsDebugPlugin::default(),
+            SceneAssetsPlugin::new(AppState::SpawningScene),
+        ))
+        .add_systems(Startup, spawn_camera)
+        .add_systems(
+            Update,
+            spawn_obstacles.run_if(
+                resource_exists::<TerrainNoise>().and_then(resource_exists::<TextureAssets>()),
+            ),
+        );
+    })
+    .run();
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0., -10., 10.).looking_at(Vec3::new(0., -40., 40.), Vec3::Y),
+        ..Default::default()
+    });
+}
+
+// line up one of each kind across the hill
+fn spawn_obstacles(
+    mut commands: Commands,
+    mut spawned: Local<bool>,
+    level_query: Query<&Level>,
+    noise: Res<TerrainNoise>,
+    textures: Res<TextureAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if *spawned {
+        return;
+    }
+    let Ok(level) = level_query.get_single() else {
+        return;
+    };
+    for (index, kind) in KINDS.into_iter().enumerate() {
+        Obstacle::new(kind, Vertex::new(index as i32 * 8 - 32, 20)).spawn(
+            level,
+            &noise.get(),
+            &mut commands,
+            &textures,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+    *spawned = true;
+}
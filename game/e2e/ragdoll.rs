@@ -8,7 +8,9 @@ use bevy_xpbd_3d::{
     resources::Gravity,
 };
 
-use cheese_game::{AppState, Cheese, CheeseRacePlugin, Person, SceneAssets};
+use cheese_game::{
+    AppState, Cheese, CheeseKind, CheeseRacePlugin, Person, PersonArchetype, SceneAssets,
+};
 
 fn main() {
     Test::new("Ragdoll".to_string(), |app| {
@@ -64,6 +66,20 @@ fn spawn_scene(
         &mut meshes,
         &mut materials,
     );
+    Person::new(2., 2.)
+        .with_archetype(PersonArchetype::Dog)
+        .spawn_ragdoll(
+            Vec3::new(-3., 0., -3.),
+            Vec3::ZERO,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
 
-    commands.spawn(Cheese::bundle(Cheese::default_transform(), &scenes));
+    Cheese::spawn(
+        CheeseKind::default(),
+        Cheese::default_transform(),
+        &scenes,
+        &mut commands,
+    );
 }
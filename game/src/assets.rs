@@ -9,7 +9,7 @@ use bevy_kira_audio::{
     AudioControl,
 };
 
-use crate::{despawn_all_recursive, AppState};
+use crate::{despawn_all_recursive, AppState, LakituWarning};
 
 pub struct SceneAssetsPlugin {
     // allow tests to continue straight to other states
@@ -51,6 +51,10 @@ impl Plugin for SceneAssetsPlugin {
                 OnEnter(AppState::SpawningScene),
                 // after we play once, just keep the loop going forever
                 play_bg_music.run_if(run_once()),
+            )
+            .add_systems(
+                Update,
+                play_lakitu_whistle.run_if(in_state(AppState::Racing)),
             );
     }
 }
@@ -102,6 +106,9 @@ pub struct AudioAssets {
     #[cfg_attr(not(target_arch = "wasm32"), asset(path = "audio/CheeseOnTheMoon.wav"))]
     #[cfg_attr(target_arch = "wasm32", asset(path = "audio/CheeseOnTheMoon.mp3"))]
     pub bg_track: Handle<AudioSource>,
+    // played when the lakitu is about to drop more pursuers
+    #[asset(path = "audio/LakituWhistle.wav")]
+    pub lakitu_whistle: Handle<AudioSource>,
 }
 
 fn play_bg_music(audio: Res<Audio>, bg_track: Res<AudioAssets>) {
@@ -111,6 +118,19 @@ fn play_bg_music(audio: Res<Audio>, bg_track: Res<AudioAssets>) {
         .with_volume(0.5);
 }
 
+fn play_lakitu_whistle(
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut warnings: EventReader<LakituWarning>,
+) {
+    // bursts can't overlap, so one whistle covers every warning this frame
+    if warnings.read().count() > 0 {
+        audio
+            .play(audio_assets.lakitu_whistle.clone())
+            .with_volume(0.6);
+    }
+}
+
 #[derive(Component)]
 struct LoadingUI;
 #[derive(Component)]
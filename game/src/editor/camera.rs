@@ -0,0 +1,108 @@
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+use crate::Level;
+
+use super::EditorScene;
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct EditorCamera {
+    yaw: f32,
+    pitch: f32,
+}
+
+impl EditorCamera {
+    const LOOK_SENSITIVITY: f32 = 0.003;
+    const SPEED: f32 = 30.;
+    const FAST_SPEED: f32 = 120.;
+    const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+    fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.)
+    }
+}
+
+impl Default for EditorCamera {
+    fn default() -> Self {
+        // look downhill
+        Self {
+            yaw: std::f32::consts::PI,
+            pitch: -0.6,
+        }
+    }
+}
+
+pub(super) fn spawn_editor_camera(mut commands: Commands) {
+    let camera = EditorCamera::default();
+    commands.spawn((
+        Name::new("Editor Camera"),
+        EditorScene,
+        camera,
+        Camera3dBundle {
+            transform: Transform::from_xyz(0., 30., 20.).with_rotation(camera.rotation()),
+            ..Default::default()
+        },
+    ));
+}
+
+// WASD to move, Q and E to sink and rise, hold the right mouse button to look around
+pub(super) fn fly_camera(
+    inputs: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &mut EditorCamera)>,
+) {
+    let look_delta: Vec2 = motion.read().map(|motion| motion.delta).sum();
+    let Ok((mut transform, mut camera)) = query.get_single_mut() else {
+        return;
+    };
+
+    if mouse.pressed(MouseButton::Right) {
+        camera.yaw -= look_delta.x * EditorCamera::LOOK_SENSITIVITY;
+        camera.pitch = (camera.pitch - look_delta.y * EditorCamera::LOOK_SENSITIVITY)
+            .clamp(-EditorCamera::MAX_PITCH, EditorCamera::MAX_PITCH);
+    }
+    transform.rotation = camera.rotation();
+
+    let mut direction = Vec3::ZERO;
+    if inputs.pressed(KeyCode::W) || inputs.pressed(KeyCode::Up) {
+        direction += transform.forward();
+    }
+    if inputs.pressed(KeyCode::S) || inputs.pressed(KeyCode::Down) {
+        direction += transform.back();
+    }
+    if inputs.pressed(KeyCode::A) || inputs.pressed(KeyCode::Left) {
+        direction += transform.left();
+    }
+    if inputs.pressed(KeyCode::D) || inputs.pressed(KeyCode::Right) {
+        direction += transform.right();
+    }
+    if inputs.pressed(KeyCode::E) {
+        direction += Vec3::Y;
+    }
+    if inputs.pressed(KeyCode::Q) {
+        direction += Vec3::NEG_Y;
+    }
+    let speed = if inputs.pressed(KeyCode::ShiftLeft) {
+        EditorCamera::FAST_SPEED
+    } else {
+        EditorCamera::SPEED
+    };
+    transform.translation += direction.normalize_or_zero() * speed * time.delta_seconds();
+}
+
+// there is no cheese in the editor, so stream chunks around the camera instead
+pub(super) fn follow_camera(
+    mut level_query: Query<&mut Level>,
+    camera_query: Query<&Transform, With<EditorCamera>>,
+) {
+    let Ok(mut level) = level_query.get_single_mut() else {
+        return;
+    };
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    level.update(camera_transform.translation);
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::{despawn_all_recursive, AppState, Course, Level, TerrainChunk};
+
+mod camera;
+use camera::*;
+
+mod tools;
+use tools::*;
+
+mod ui;
+use ui::*;
+
+// Marks a race that was launched from the editor, so that quitting returns to the editor
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub struct PlayTest;
+
+// Where the editor saves the course it is working on
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub(super) struct EditorSavePath(pub PathBuf);
+
+impl Default for EditorSavePath {
+    fn default() -> Self {
+        Self(PathBuf::from(Course::DEFAULT_PATH))
+    }
+}
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorTool>()
+            .init_resource::<EditorSelection>()
+            .init_resource::<EditorStatus>()
+            .init_resource::<EditorSavePath>()
+            .add_systems(OnEnter(AppState::Menu), clear_course)
+            .add_systems(
+                OnEnter(AppState::Editor),
+                (
+                    load_course,
+                    spawn_editor_scene,
+                    spawn_editor_camera,
+                    spawn_editor_ui,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    fly_camera,
+                    follow_camera,
+                    handle_palette,
+                    highlight_selected_tool,
+                    apply_tool,
+                    respawn_pieces.run_if(resource_exists_and_changed::<Course>()),
+                    track_status_text,
+                    handle_save,
+                    handle_play_test,
+                    handle_exit,
+                )
+                    .run_if(in_state(AppState::Editor)),
+            )
+            .add_systems(
+                OnExit(AppState::Editor),
+                (
+                    save_course,
+                    apply_deferred,
+                    (
+                        despawn_all_recursive::<EditorScene>,
+                        despawn_all_recursive::<EditorPiece>,
+                        despawn_all_recursive::<EditorUI>,
+                        despawn_all_recursive::<TerrainChunk>,
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Marks everything spawned for the editor's 3D scene
+#[derive(Component)]
+pub(super) struct EditorScene;
+
+fn clear_course(mut commands: Commands) {
+    commands.remove_resource::<Course>();
+    commands.remove_resource::<PlayTest>();
+}
+
+fn load_course(
+    mut commands: Commands,
+    course: Option<ResMut<Course>>,
+    mut selection: ResMut<EditorSelection>,
+    mut status: ResMut<EditorStatus>,
+    mut save_path: ResMut<EditorSavePath>,
+) {
+    selection.0 = None;
+    if let Some(mut course) = course {
+        // coming back from a play-test, so make sure the pieces get spawned again
+        commands.insert_resource(course.terrain_noise());
+        course.set_changed();
+        status.0 = "Back from play-testing".to_string();
+        return;
+    }
+
+    *save_path = EditorSavePath::default();
+    let course = match Course::load(Course::DEFAULT_PATH) {
+        Ok(course) => {
+            status.0 = format!("Loaded {}", Course::DEFAULT_PATH);
+            course
+        }
+        Err(error) if error.is_not_found() => {
+            info!("Starting a new course: {}", error);
+            status.0 = "New course".to_string();
+            Course::new(rand::random())
+        }
+        Err(error) => {
+            // keep the file that failed to load, in case it can still be fixed by hand
+            save_path.0 = PathBuf::from(Course::BACKUP_PATH);
+            error!("Failed to load {}: {}", Course::DEFAULT_PATH, error);
+            status.0 = format!(
+                "Could not load {}: {}. Saving to {} instead",
+                Course::DEFAULT_PATH,
+                error,
+                Course::BACKUP_PATH,
+            );
+            Course::new(rand::random())
+        }
+    };
+    commands.insert_resource(course.terrain_noise());
+    commands.insert_resource(course);
+}
+
+fn save_course(
+    course: Res<Course>,
+    save_path: Res<EditorSavePath>,
+    mut status: ResMut<EditorStatus>,
+) {
+    status.0 = match course.save(&save_path.0) {
+        Ok(()) => format!("Saved to {}", save_path.0.display()),
+        Err(error) => {
+            warn!("Failed to save course: {}", error);
+            format!("Failed to save: {}", error)
+        }
+    };
+}
+
+fn spawn_editor_scene(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Editor Lighting"),
+        EditorScene,
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: 10.0e3,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(2., 10., 5.).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((Level::default(), Level::name(), EditorScene));
+}
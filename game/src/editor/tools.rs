@@ -0,0 +1,226 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{
+    Course, CoursePiece, HeightModifier, Level, ObstacleKind, Terrain, TextureAssets, Vertex,
+};
+
+use super::EditorCamera;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Resource)]
+pub(super) enum EditorTool {
+    Place(ObstacleKind),
+    Move,
+    Erase,
+    Raise,
+    Lower,
+}
+
+impl Default for EditorTool {
+    fn default() -> Self {
+        Self::PALETTE[0]
+    }
+}
+
+impl EditorTool {
+    pub(super) const PALETTE: [EditorTool; 17] = [
+        EditorTool::Place(ObstacleKind::Wall { width: 20 }),
+        EditorTool::Place(ObstacleKind::Wall { width: 6 }),
+        EditorTool::Place(ObstacleKind::Fence { width: 12 }),
+        EditorTool::Place(ObstacleKind::Post),
+        EditorTool::Place(ObstacleKind::Pillar),
+        EditorTool::Place(ObstacleKind::HayBale),
+        EditorTool::Place(ObstacleKind::Boulder),
+        EditorTool::Place(ObstacleKind::Stump),
+        EditorTool::Place(ObstacleKind::RollingLog),
+        EditorTool::Place(ObstacleKind::SwingingGate),
+        EditorTool::Place(ObstacleKind::SlidingBarrier),
+        EditorTool::Place(ObstacleKind::BarrelDrop),
+        EditorTool::Place(ObstacleKind::Tractor),
+        EditorTool::Move,
+        EditorTool::Erase,
+        EditorTool::Raise,
+        EditorTool::Lower,
+    ];
+
+    pub(super) fn label(&self) -> String {
+        match self {
+            EditorTool::Place(kind) => kind.label(),
+            EditorTool::Move => "Move".to_string(),
+            EditorTool::Erase => "Erase".to_string(),
+            EditorTool::Raise => "Raise".to_string(),
+            EditorTool::Lower => "Lower".to_string(),
+        }
+    }
+}
+
+// The piece picked up by the Move tool, as an index into `Course::pieces`
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub(super) struct EditorSelection(pub Option<usize>);
+
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub(super) struct EditorStatus(pub String);
+
+#[derive(Component)]
+pub(super) struct EditorPiece;
+
+// how close (in vertices) a click must be to a piece to select or erase it
+const SELECT_DISTANCE: f32 = 3.;
+// the shape of a single brush stroke when painting terrain
+const PAINT_RADIUS: f32 = 6.;
+const PAINT_HEIGHT: f32 = 1.5;
+
+// snaps a world position to the grid that course pieces are placed on
+fn grid_vertex(point: Vec3, quad_size: Vec2) -> Vertex {
+    Vertex::new(
+        (point.x / quad_size.x).round() as i32,
+        (point.z / quad_size.y).round() as i32,
+    )
+}
+
+// snaps a world position to the global vertex that the terrain samples its noise from,
+// which counts z backwards from the end of the first chunk
+fn terrain_vertex(point: Vec3, level: &Level) -> Vertex {
+    let vertex = grid_vertex(point, level.quad_size);
+    Vertex::new(vertex.x, level.chunk_size.z - vertex.z)
+}
+
+// anchor pieces so that they are centered on the clicked vertex
+fn centered_piece(kind: ObstacleKind, vertex: Vertex) -> CoursePiece {
+    CoursePiece::new(kind, vertex - Vertex::new(kind.extent() / 2, 0))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_tool(
+    mut commands: Commands,
+    mouse: Res<Input<MouseButton>>,
+    tool: Res<EditorTool>,
+    mut selection: ResMut<EditorSelection>,
+    mut status: ResMut<EditorStatus>,
+    mut course: ResMut<Course>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<EditorCamera>>,
+    interaction_query: Query<&Interaction>,
+    mut terrain_query: Query<(&mut Terrain, &Level)>,
+    spatial_query: SpatialQuery,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    // ignore clicks that land on the editor UI
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+    let Some(hit) = spatial_query.cast_ray(
+        ray.origin,
+        ray.direction,
+        2000.,
+        true,
+        SpatialQueryFilter::default(),
+    ) else {
+        return;
+    };
+    let Ok((mut terrain, level)) = terrain_query.get_single_mut() else {
+        return;
+    };
+
+    let point = ray.origin + ray.direction * hit.time_of_impact;
+    let vertex = grid_vertex(point, level.quad_size);
+    match *tool {
+        EditorTool::Place(kind) => {
+            course.pieces.push(centered_piece(kind, vertex));
+            status.0 = format!("Placed {} at ({}, {})", kind.label(), vertex.x, vertex.z);
+        }
+        EditorTool::Move => {
+            if let Some(index) = selection.0.take() {
+                if let Some(piece) = course.pieces.get_mut(index) {
+                    *piece = centered_piece(piece.kind, vertex);
+                    status.0 = format!(
+                        "Moved {} to ({}, {})",
+                        piece.kind.label(),
+                        vertex.x,
+                        vertex.z
+                    );
+                }
+            } else if let Some(index) = course.nearest_piece(vertex, SELECT_DISTANCE) {
+                selection.0 = Some(index);
+                status.0 = format!(
+                    "Selected {}, click to move it",
+                    course.pieces[index].kind.label()
+                );
+            }
+        }
+        EditorTool::Erase => {
+            if let Some(index) = course.nearest_piece(vertex, SELECT_DISTANCE) {
+                let piece = course.pieces.remove(index);
+                status.0 = format!("Erased {}", piece.kind.label());
+            }
+        }
+        EditorTool::Raise | EditorTool::Lower => {
+            let height = if *tool == EditorTool::Raise {
+                PAINT_HEIGHT
+            } else {
+                -PAINT_HEIGHT
+            };
+            course.height_modifiers.push(HeightModifier::new(
+                terrain_vertex(point, level),
+                PAINT_RADIUS,
+                height,
+            ));
+            // regenerate the terrain with the new modifier
+            commands.insert_resource(course.terrain_noise());
+            terrain.clear(&mut commands);
+        }
+    }
+}
+
+pub(super) fn respawn_pieces(
+    mut commands: Commands,
+    course: Res<Course>,
+    piece_query: Query<Entity, With<EditorPiece>>,
+    level_query: Query<&Level>,
+    textures: Res<TextureAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Ok(level) = level_query.get_single() else {
+        return;
+    };
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    // the course's noise already includes any edits made this frame
+    let terrain_noise = course.terrain_noise();
+    for piece in course.pieces.iter() {
+        let entity = piece.spawn(
+            level,
+            &terrain_noise.get(),
+            &mut commands,
+            &textures,
+            &mut meshes,
+            &mut materials,
+        );
+        // keep rolling obstacles where they were placed until the course is play-tested
+        commands
+            .entity(entity)
+            .insert((EditorPiece, RigidBody::Static));
+    }
+}
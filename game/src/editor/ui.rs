@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+
+use crate::{button, AppState, Course};
+
+use super::{EditorSavePath, EditorSelection, EditorStatus, EditorTool, PlayTest};
+
+#[derive(Component)]
+pub(super) struct EditorUI;
+#[derive(Component)]
+pub(super) struct EditorStatusText;
+#[derive(Component)]
+pub(super) struct PaletteButton(EditorTool);
+#[derive(Component)]
+pub(super) struct SaveButton;
+#[derive(Component)]
+pub(super) struct PlayTestButton;
+#[derive(Component)]
+pub(super) struct ExitButton;
+
+const SELECTED_BORDER: Color = Color::rgb(0.95, 0.85, 0.3);
+
+pub(super) fn spawn_editor_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Editor UI"),
+            EditorUI,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    padding: UiRect::all(Val::Percent(2.)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder
+                .spawn((
+                    Name::new("Palette"),
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            // wrap into more columns when the palette is taller than the window
+                            flex_wrap: FlexWrap::Wrap,
+                            align_content: AlignContent::FlexStart,
+                            row_gap: Val::Px(8.),
+                            column_gap: Val::Px(8.),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|builder| {
+                    for tool in EditorTool::PALETTE {
+                        builder
+                            .spawn((
+                                Name::new(format!("{} Button", tool.label())),
+                                PaletteButton(tool),
+                                button(),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(button_text(tool.label()));
+                            });
+                    }
+                });
+            builder
+                .spawn((
+                    Name::new("Editor Sidebar"),
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(40.0),
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::FlexEnd,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|builder| {
+                    builder.spawn((
+                        Name::new("Editor Status"),
+                        EditorStatusText,
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 28.,
+                                color: Color::rgba(1., 1., 1., 0.85),
+                                ..Default::default()
+                            },
+                        ),
+                    ));
+                    builder.spawn((Name::new("Editor Instructions"), instructions()));
+                    builder
+                        .spawn((
+                            Name::new("Editor Actions"),
+                            NodeBundle {
+                                style: Style {
+                                    column_gap: Val::Px(8.),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|builder| {
+                            builder
+                                .spawn((Name::new("Save Button"), SaveButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn(button_text("Save".to_string()));
+                                });
+                            builder
+                                .spawn((Name::new("Play-test Button"), PlayTestButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn(button_text("Play-test".to_string()));
+                                });
+                            builder
+                                .spawn((Name::new("Exit Button"), ExitButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn(button_text("Menu".to_string()));
+                                });
+                        });
+                });
+        });
+}
+
+fn button_text(label: String) -> TextBundle {
+    TextBundle::from_section(
+        label,
+        TextStyle {
+            font_size: 24.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+            ..Default::default()
+        },
+    )
+}
+
+fn instructions() -> TextBundle {
+    TextBundle::from_section(
+        "Fly with WASD, Q and E. Hold Shift to fly faster.\n\
+        Hold the right mouse button to look around.\n\
+        Click on the hill to use the selected tool.",
+        TextStyle {
+            font_size: 20.,
+            color: Color::rgba(1., 1., 1., 0.75),
+            ..Default::default()
+        },
+    )
+}
+
+pub(super) fn track_status_text(
+    mut ui_query: Query<&mut Text, With<EditorStatusText>>,
+    status: Res<EditorStatus>,
+    tool: Res<EditorTool>,
+) {
+    if !status.is_changed() && !tool.is_changed() {
+        return;
+    }
+    let Ok(mut text) = ui_query.get_single_mut() else {
+        return;
+    };
+    text.sections[0].value = format!("Tool: {}\n{}", tool.label(), status.0);
+}
+
+pub(super) fn handle_palette(
+    interaction_query: Query<(&Interaction, &PaletteButton), Changed<Interaction>>,
+    mut tool: ResMut<EditorTool>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            *tool = button.0;
+            selection.0 = None;
+        }
+    }
+}
+
+pub(super) fn highlight_selected_tool(
+    mut button_query: Query<(&PaletteButton, &mut BorderColor)>,
+    tool: Res<EditorTool>,
+) {
+    for (button, mut border) in button_query.iter_mut() {
+        border.0 = if button.0 == *tool {
+            SELECTED_BORDER
+        } else {
+            Color::BLACK
+        };
+    }
+}
+
+pub(super) fn handle_save(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SaveButton>)>,
+    course: Res<Course>,
+    save_path: Res<EditorSavePath>,
+    mut status: ResMut<EditorStatus>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            status.0 = match course.save(&save_path.0) {
+                Ok(()) => format!("Saved to {}", save_path.0.display()),
+                Err(error) => format!("Failed to save: {}", error),
+            };
+        }
+    }
+}
+
+pub(super) fn handle_play_test(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayTestButton>)>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            // the race spawns from the `Course` resource and heads straight into the countdown
+            commands.insert_resource(PlayTest);
+            state.set(AppState::SpawningScene);
+        }
+    }
+}
+
+pub(super) fn handle_exit(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ExitButton>)>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            state.set(AppState::Menu);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+// tools for exporting generated courses without a window or GPU
+
+use std::{io::Write, path::Path};
+
+use derive_more::From;
+use itertools::Itertools;
+
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+use crate::{
+    Chunk, DifficultyCurve, Level, Obstacle, ObstacleNoise, ObstacleRegistry, Obstacles,
+    TerrainChunk, TerrainNoise, Vertex,
+};
+
+// A rectangle of level chunks to sample, inclusive of both corners
+#[derive(Clone, Debug)]
+pub struct TerrainExport {
+    pub min_chunk: Vertex,
+    pub max_chunk: Vertex,
+    pub chunk_size: Vertex,
+    pub quad_size: Vec2,
+    pub registry: ObstacleRegistry,
+    pub difficulty: DifficultyCurve,
+}
+
+// Heights sampled on the vertex grid of an exported chunk rectangle, in world units
+#[derive(Clone, Debug)]
+pub struct Heightmap {
+    pub width: usize,
+    pub depth: usize,
+    // row-major, with rows running down the hill
+    pub positions: Vec<Vec3>,
+}
+
+impl Heightmap {
+    pub fn min_height(&self) -> f32 {
+        self.positions
+            .iter()
+            .map(|position| position.y)
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn max_height(&self) -> f32 {
+        self.positions
+            .iter()
+            .map(|position| position.y)
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+}
+
+impl TerrainExport {
+    pub fn new(corner: Vertex, other_corner: Vertex) -> Self {
+        let level = Level::default();
+        Self {
+            min_chunk: Vertex::new(corner.x.min(other_corner.x), corner.z.min(other_corner.z)),
+            max_chunk: Vertex::new(corner.x.max(other_corner.x), corner.z.max(other_corner.z)),
+            chunk_size: level.chunk_size,
+            quad_size: level.quad_size,
+            registry: ObstacleRegistry::default(),
+            difficulty: DifficultyCurve::default(),
+        }
+    }
+
+    pub fn with_registry(mut self, registry: ObstacleRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn with_difficulty(mut self, difficulty: DifficultyCurve) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    fn chunk_origins(&self) -> impl Iterator<Item = Vertex> {
+        (self.min_chunk.x..=self.max_chunk.x)
+            .cartesian_product(self.min_chunk.z..=self.max_chunk.z)
+            .map(|(x, z)| Vertex::new(x, z))
+    }
+
+    pub fn sample_heightmap(&self, noise: &TerrainNoise) -> Heightmap {
+        let chunks_x = self.max_chunk.x - self.min_chunk.x + 1;
+        let chunks_z = self.max_chunk.z - self.min_chunk.z + 1;
+        let width = (chunks_x * self.chunk_size.x + 1) as usize;
+        let depth = (chunks_z * self.chunk_size.z + 1) as usize;
+        let mut positions = vec![Vec3::ZERO; width * depth];
+
+        // level chunk z counts up the hill, so the chunk at max_chunk.z holds the first row
+        for origin in self.chunk_origins() {
+            let terrain_chunk =
+                TerrainChunk::new(Chunk::new(origin, self.chunk_size, self.quad_size));
+            let translation = terrain_chunk.translation();
+            for vertex in terrain_chunk.chunk.iter_by_row() {
+                let column = (origin.x - self.min_chunk.x) * self.chunk_size.x + vertex.x;
+                let row = (self.max_chunk.z - origin.z) * self.chunk_size.z + vertex.z;
+                // neighbouring chunks share their edge vertices, so overwriting is harmless
+                positions[row as usize * width + column as usize] =
+                    translation + terrain_chunk.vertex_position(vertex, &noise.get());
+            }
+        }
+
+        Heightmap {
+            width,
+            depth,
+            positions,
+        }
+    }
+
+    // the obstacles a race with this seed would place
+    pub fn generate_obstacles(&self, seed: u32) -> Vec<Obstacle> {
+        let noise = ObstacleNoise::new(seed);
+        self.chunk_origins()
+            .flat_map(|origin| {
+                let chunk =
+                    Obstacles::chunk_for_level_origin(origin, self.chunk_size, self.quad_size);
+                Obstacles::generate_obstacles_for_chunk(
+                    chunk,
+                    seed,
+                    &noise.get(),
+                    &self.registry,
+                    &self.difficulty,
+                )
+                .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    // writes a 16-bit grayscale PNG where black is the lowest point and white the highest,
+    // returning the range of heights that the image spans
+    pub fn write_heightmap_png(
+        &self,
+        noise: &TerrainNoise,
+        path: impl AsRef<Path>,
+    ) -> Result<(f32, f32), ExportError> {
+        let heightmap = self.sample_heightmap(noise);
+        let min = heightmap.min_height();
+        let max = heightmap.max_height();
+        let range = (max - min).max(f32::EPSILON);
+        let pixels = heightmap
+            .positions
+            .iter()
+            .map(|position| ((position.y - min) / range * u16::MAX as f32).round() as u16)
+            .collect::<Vec<_>>();
+        let image = image::ImageBuffer::<image::Luma<u16>, _>::from_raw(
+            heightmap.width as u32,
+            heightmap.depth as u32,
+            pixels,
+        )
+        .expect("the heightmap should have a height for every pixel");
+        image.save_with_format(path, image::ImageFormat::Png)?;
+        Ok((min, max))
+    }
+
+    // writes the terrain surface as a Wavefront OBJ, optionally with the generated obstacles
+    pub fn write_obj(
+        &self,
+        terrain_noise: &TerrainNoise,
+        obstacle_seed: Option<u32>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ExportError> {
+        let heightmap = self.sample_heightmap(terrain_noise);
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+        writeln!(file, "# Cheese Rolling: Forever terrain export")?;
+        writeln!(file, "o Terrain")?;
+        for position in heightmap.positions.iter() {
+            writeln!(file, "v {} {} {}", position.x, position.y, position.z)?;
+        }
+        // OBJ indices start at 1
+        for (row, column) in (0..heightmap.depth - 1).cartesian_product(0..heightmap.width - 1) {
+            let index = row * heightmap.width + column + 1;
+            let below = index + heightmap.width;
+            writeln!(file, "f {} {} {}", below + 1, index + 1, below)?;
+            writeln!(file, "f {} {} {}", index, below, index + 1)?;
+        }
+
+        let mut vertex_count = heightmap.positions.len();
+        if let Some(obstacle_seed) = obstacle_seed {
+            for obstacle in self.generate_obstacles(obstacle_seed) {
+                writeln!(
+                    file,
+                    "o {}_{}_{}",
+                    obstacle.kind.label().replace(' ', ""),
+                    obstacle.vertex.x,
+                    obstacle.vertex.z
+                )?;
+                let transform =
+                    obstacle.transform(self.chunk_size, self.quad_size, &terrain_noise.get());
+                for part in obstacle.kind.parts(self.quad_size) {
+                    let part_transform = transform * part.transform;
+                    let Some(VertexAttributeValues::Float32x3(positions)) =
+                        part.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                    else {
+                        continue;
+                    };
+                    for position in positions.iter() {
+                        let position = part_transform.transform_point(Vec3::from(*position));
+                        writeln!(file, "v {} {} {}", position.x, position.y, position.z)?;
+                    }
+                    if let Some(indices) = part.mesh.indices() {
+                        for triangle in indices.iter().chunks(3).into_iter() {
+                            let triangle = triangle
+                                .map(|index| (vertex_count + index + 1).to_string())
+                                .join(" ");
+                            writeln!(file, "f {}", triangle)?;
+                        }
+                    }
+                    vertex_count += positions.len();
+                }
+            }
+        }
+
+        file.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, From)]
+pub enum ExportError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Io(error) => write!(f, "could not write export: {}", error),
+            ExportError::Image(error) => write!(f, "could not encode heightmap: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
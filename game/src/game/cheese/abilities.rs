@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use super::{CheeseCondition, CheeseStats};
+
+// The hop and brake that the player can use on top of steering
+#[derive(Clone, Debug)]
+#[derive(Component, Reflect)]
+pub struct CheeseAbilities {
+    pub hop_cooldown: Timer,
+    pub braking: bool,
+}
+
+impl CheeseAbilities {
+    // the upward change in speed from a hop
+    pub const HOP_SPEED: f32 = 6.;
+    pub const HOP_COOLDOWN: f32 = 1.5;
+    // braking grips the hill harder and stops the wheel spinning so freely
+    pub const BRAKE_FRICTION: f32 = 3.;
+    pub const BRAKE_ANGULAR_DAMPING: f32 = 4.;
+
+    pub fn can_hop(&self) -> bool {
+        self.hop_cooldown.finished()
+    }
+
+    pub fn hop(&mut self) {
+        self.hop_cooldown.reset();
+    }
+
+    // how much of the cooldown is left, from 1 just after a hop down to 0 when it is ready
+    pub fn hop_cooldown_fraction(&self) -> f32 {
+        self.hop_cooldown.percent_left()
+    }
+
+    pub fn friction(&self, stats: &CheeseStats) -> Friction {
+        if self.braking {
+            Friction::new(stats.friction * Self::BRAKE_FRICTION)
+        } else {
+            Friction::new(stats.friction)
+        }
+    }
+
+    // braking stacks on top of the wobble from any damage
+    pub fn angular_damping(
+        &self,
+        stats: &CheeseStats,
+        condition: &CheeseCondition,
+    ) -> AngularDamping {
+        let damping = condition.angular_damping(stats);
+        if self.braking {
+            AngularDamping(damping.0 * Self::BRAKE_ANGULAR_DAMPING)
+        } else {
+            damping
+        }
+    }
+}
+
+impl Default for CheeseAbilities {
+    fn default() -> Self {
+        let mut hop_cooldown = Timer::from_seconds(Self::HOP_COOLDOWN, TimerMode::Once);
+        // the hop is ready from the start
+        hop_cooldown.tick(hop_cooldown.duration());
+        Self {
+            hop_cooldown,
+            braking: false,
+        }
+    }
+}
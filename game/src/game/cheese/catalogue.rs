@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use super::Cheese;
+
+// The cheeses that can be raced down the hill
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Component, Reflect)]
+pub enum CheeseKind {
+    #[default]
+    DoubleGloucester,
+    RedLeicester,
+    Stilton,
+    Brie,
+}
+
+// How a cheese is shaped and how it handles
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub struct CheeseStats {
+    pub height: f32,
+    pub radius: f32,
+    pub density: f32,
+    pub friction: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    // scales the impulses applied when steering
+    pub steering: f32,
+    // divides the damage taken from hard hits
+    pub toughness: f32,
+}
+
+impl CheeseKind {
+    pub const ALL: [CheeseKind; 4] = [
+        CheeseKind::DoubleGloucester,
+        CheeseKind::RedLeicester,
+        CheeseKind::Stilton,
+        CheeseKind::Brie,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheeseKind::DoubleGloucester => "Double Gloucester",
+            CheeseKind::RedLeicester => "Red Leicester",
+            CheeseKind::Stilton => "Stilton",
+            CheeseKind::Brie => "Brie",
+        }
+    }
+
+    // multiplies the colours of the cheese model, which is made to look like a Double Gloucester
+    pub fn tint(&self) -> Color {
+        match self {
+            CheeseKind::DoubleGloucester => Color::WHITE,
+            CheeseKind::RedLeicester => Color::rgb(1., 0.5, 0.25),
+            CheeseKind::Stilton => Color::rgb(0.72, 0.76, 0.8),
+            CheeseKind::Brie => Color::rgb(1., 0.96, 0.75),
+        }
+    }
+
+    pub fn stats(&self) -> CheeseStats {
+        match self {
+            // the traditional cheese, which the others are measured against
+            CheeseKind::DoubleGloucester => CheeseStats {
+                height: Cheese::HEIGHT,
+                radius: Cheese::RADIUS,
+                density: 900.,
+                friction: 0.5,
+                linear_damping: 0.08,
+                angular_damping: 0.08,
+                steering: 1.,
+                toughness: 1.,
+            },
+            // small and dense, so it shrugs off knocks but is slow to turn
+            CheeseKind::RedLeicester => CheeseStats {
+                height: 0.32,
+                radius: 0.45,
+                density: 1500.,
+                friction: 0.45,
+                linear_damping: 0.05,
+                angular_damping: 0.05,
+                steering: 0.8,
+                toughness: 1.5,
+            },
+            // a broad, stable wheel that grips the hill
+            CheeseKind::Stilton => CheeseStats {
+                height: 0.6,
+                radius: 0.7,
+                density: 800.,
+                friction: 0.7,
+                linear_damping: 0.1,
+                angular_damping: 0.12,
+                steering: 1.4,
+                toughness: 1.2,
+            },
+            // light and soft, which soaks up speed but turns on a dime
+            CheeseKind::Brie => CheeseStats {
+                height: 0.24,
+                radius: 0.62,
+                density: 650.,
+                friction: 0.9,
+                linear_damping: 0.16,
+                angular_damping: 0.2,
+                steering: 1.3,
+                toughness: 0.6,
+            },
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        self.offset(1)
+    }
+
+    pub fn previous(&self) -> Self {
+        self.offset(Self::ALL.len() - 1)
+    }
+
+    fn offset(&self, offset: usize) -> Self {
+        let index = Self::ALL.iter().position(|kind| kind == self);
+        Self::ALL[(index.unwrap_or_default() + offset) % Self::ALL.len()]
+    }
+}
+
+impl CheeseStats {
+    pub fn collider(&self) -> Collider {
+        Collider::cylinder(self.height, self.radius)
+    }
+
+    // the cheese models are all made at the size of a Double Gloucester
+    pub fn model_scale(&self) -> Vec3 {
+        Vec3::new(
+            self.radius / Cheese::RADIUS,
+            self.height / Cheese::HEIGHT,
+            self.radius / Cheese::RADIUS,
+        )
+    }
+}
+
+// The cheese that the next race will be run with
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource, Reflect)]
+pub struct SelectedCheese(pub CheeseKind);
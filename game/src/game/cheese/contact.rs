@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+// What the cheese is rolling on, refreshed after every physics step
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+pub struct CheeseContact {
+    // the surface normal under the cheese, or straight up while it is in the air
+    pub normal: Vec3,
+    pub grounded: bool,
+    // seconds since the cheese last touched the ground
+    pub airtime: f32,
+    // whether the cheese hopped into the air, rather than leaving the ground by itself
+    pub hopped: bool,
+}
+
+impl CheeseContact {
+    // how far the cheese's collider can be from the ground while still counting as touching it
+    pub const MARGIN: f32 = 0.1;
+    // how much of the steering still works in the air
+    pub const AIR_CONTROL: f32 = 0.25;
+
+    // how long the cheese can be off the ground and still steer and hop as if it weren't, so that
+    // skipping over small bumps doesn't cost any control
+    pub const GRACE_TIME: f32 = 0.15;
+
+    // whether the cheese is on the ground, or has only just left it
+    pub fn on_ground(&self) -> bool {
+        self.grounded || self.airtime < Self::GRACE_TIME
+    }
+
+    // how much of the steering works, since the cheese can only push off the ground
+    pub fn control(&self) -> f32 {
+        if self.on_ground() {
+            1.
+        } else {
+            Self::AIR_CONTROL
+        }
+    }
+}
+
+impl Default for CheeseContact {
+    fn default() -> Self {
+        Self {
+            normal: Vec3::Y,
+            grounded: false,
+            airtime: 0.,
+            hopped: false,
+        }
+    }
+}
+
+// how fast the cheese was heading into the body it touches in `contacts`, along the normal of
+// the contact, so that glancing scrapes count for less than head-on hits. `velocity` is the
+// cheese's velocity relative to the other body from before the contact was solved
+pub fn impact_speed(
+    contacts: &Contacts,
+    cheese: Entity,
+    rotation: &Rotation,
+    velocity: Vec3,
+) -> f32 {
+    contacts
+        .manifolds
+        .iter()
+        .flat_map(|manifold| manifold.contacts.iter())
+        .map(|contact| {
+            let normal = if contacts.entity1 == cheese {
+                contact.global_normal1(rotation)
+            } else {
+                contact.global_normal2(rotation)
+            };
+            velocity.dot(normal).abs()
+        })
+        .fold(0., f32::max)
+}
+
+// Sent when the cheese leaves the ground
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct CheeseTakeoff {
+    pub cheese: Entity,
+}
+
+// Sent when the cheese comes back down, with how long it was in the air
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct CheeseLanding {
+    pub cheese: Entity,
+    pub airtime: f32,
+    // whether the cheese got there by hopping
+    pub hopped: bool,
+}
@@ -0,0 +1,153 @@
+use rand::Rng;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{Debris, GameLayer, SceneAssets};
+
+use super::{CheeseKind, CheeseStats};
+
+// How much of the cheese is left, from 1 when whole down to 0 when it falls apart
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+pub struct CheeseHealth(pub f32);
+
+impl CheeseHealth {
+    // hits slower than this along the contact normal don't hurt the cheese
+    pub const DAMAGE_THRESHOLD: f32 = 6.;
+    // how much health is lost for each unit of impact speed above the threshold
+    pub const DAMAGE_PER_SPEED: f32 = 0.025;
+
+    // the health lost to a hit where the cheese ran into something at `impact_speed`, see
+    // `impact_speed`
+    pub fn damage(impact_speed: f32, stats: &CheeseStats) -> f32 {
+        (impact_speed - Self::DAMAGE_THRESHOLD).max(0.) * Self::DAMAGE_PER_SPEED / stats.toughness
+    }
+}
+
+impl Default for CheeseHealth {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+// The states that a cheese passes through as it takes damage
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Component, Reflect)]
+pub enum CheeseCondition {
+    #[default]
+    Good,
+    Ok,
+    Crumbling,
+    Broken,
+}
+
+impl CheeseCondition {
+    pub fn from_health(health: &CheeseHealth) -> Self {
+        if health.0 > 0.66 {
+            CheeseCondition::Good
+        } else if health.0 > 0.33 {
+            CheeseCondition::Ok
+        } else if health.0 > 0. {
+            CheeseCondition::Crumbling
+        } else {
+            CheeseCondition::Broken
+        }
+    }
+
+    // how much of the whole cheese's size is left
+    pub fn scale(&self) -> f32 {
+        match self {
+            CheeseCondition::Good => 1.,
+            CheeseCondition::Ok => 0.9,
+            CheeseCondition::Crumbling => 0.8,
+            CheeseCondition::Broken => 0.,
+        }
+    }
+
+    // a chipped wheel wobbles and loses spin faster
+    pub fn angular_damping(&self, stats: &CheeseStats) -> AngularDamping {
+        let multiplier = match self {
+            CheeseCondition::Good => 1.,
+            CheeseCondition::Ok => 2.,
+            CheeseCondition::Crumbling | CheeseCondition::Broken => 4.,
+        };
+        AngularDamping(stats.angular_damping * multiplier)
+    }
+
+    pub fn collider(&self, stats: &CheeseStats) -> Collider {
+        Collider::cylinder(stats.height * self.scale(), stats.radius * self.scale())
+    }
+
+    pub fn model(&self, scenes: &SceneAssets) -> Handle<Scene> {
+        match self {
+            CheeseCondition::Good => scenes.cheese_good.clone(),
+            _ => scenes.cheese_ok.clone(),
+        }
+    }
+
+    // how many chunks break off when the cheese falls into this condition
+    pub fn chunks_lost(&self) -> usize {
+        match self {
+            CheeseCondition::Good => 0,
+            CheeseCondition::Ok => 2,
+            CheeseCondition::Crumbling => 3,
+            CheeseCondition::Broken => 8,
+        }
+    }
+
+    // knocks chunks off around the rim of the cheese, which keep rolling along with it
+    pub fn spawn_chunks(
+        &self,
+        transform: &Transform,
+        velocity: Vec3,
+        stats: &CheeseStats,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        const SCATTER_SPEED: f32 = 3.;
+        let count = self.chunks_lost();
+        if count == 0 {
+            return;
+        }
+        let size = Vec3::new(stats.radius * 0.5, stats.height * 0.8, stats.radius * 0.5);
+        let mesh = meshes.add(shape::Box::new(size.x, size.y, size.z).into());
+        let material = materials.add(Color::rgb(0.96, 0.76, 0.32).into());
+        let mut rng = rand::thread_rng();
+        for index in 0..count {
+            let angle =
+                std::f32::consts::TAU * (index as f32 + rng.gen_range(0.0..0.5)) / count as f32;
+            // the cheese's axis is its local Y, so the rim lies in its local XZ plane.
+            // chunks start just outside it so that they don't overlap the cheese's collider
+            let rim = Vec3::new(angle.cos(), 0., angle.sin()) * stats.radius * 1.3;
+            let outward = (transform.rotation * rim).normalize_or_zero();
+            commands.spawn((
+                Name::new("Cheese Chunk"),
+                Debris::new(),
+                RigidBody::Dynamic,
+                Collider::cuboid(size.x, size.y, size.z),
+                GameLayer::debris(),
+                ColliderDensity(stats.density),
+                LinearVelocity(velocity * 0.8 + (outward + Vec3::Y) * SCATTER_SPEED),
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_translation(transform.transform_point(rim))
+                        .with_rotation(transform.rotation),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+// Marks the cheese's model, which is swapped and shrunk as the cheese takes damage
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct CheeseModel;
+
+// Tints the materials of a cheese model to tell the kinds apart, see `CheeseKind::tint`
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct CheeseTint(pub CheeseKind);
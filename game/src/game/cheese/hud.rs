@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use super::{Cheese, CheeseAbilities, CheeseContact};
+
+const READY_COLOR: Color = Color::rgb(0.96, 0.76, 0.32);
+const WAITING_COLOR: Color = Color::rgba(0.5, 0.5, 0.5, 0.6);
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct AbilityHud;
+// Fills back up as the hop cools down
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct HopCooldownBar;
+// Lights up while the brake is held
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct BrakeIndicator;
+
+fn ability_slot(builder: &mut ChildBuilder, label: &str, indicator: impl Bundle) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(96.),
+                        height: Val::Px(16.),
+                        ..Default::default()
+                    },
+                    background_color: WAITING_COLOR.into(),
+                    ..Default::default()
+                })
+                .with_children(|builder| {
+                    builder.spawn((
+                        indicator,
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.),
+                                height: Val::Percent(100.),
+                                ..Default::default()
+                            },
+                            background_color: READY_COLOR.into(),
+                            ..Default::default()
+                        },
+                    ));
+                });
+            builder.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 32.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub(super) fn spawn_ability_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Ability HUD"),
+            AbilityHud,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(1.),
+                    bottom: Val::Percent(1.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            ability_slot(builder, "Hop", HopCooldownBar);
+            ability_slot(builder, "Brake", BrakeIndicator);
+        });
+}
+
+pub(super) fn track_ability_hud(
+    cheese_query: Query<(&CheeseAbilities, &CheeseContact), With<Cheese>>,
+    mut hop_query: Query<(&mut Style, &mut BackgroundColor), With<HopCooldownBar>>,
+    mut brake_query: Query<&mut BackgroundColor, (With<BrakeIndicator>, Without<HopCooldownBar>)>,
+) {
+    let Ok((abilities, contact)) = cheese_query.get_single() else {
+        return;
+    };
+    for (mut style, mut color) in hop_query.iter_mut() {
+        style.width = Val::Percent((1. - abilities.hop_cooldown_fraction()) * 100.);
+        // the hop only works from the ground, so it is greyed out in the air
+        color.0 = if abilities.can_hop() && contact.on_ground() {
+            READY_COLOR
+        } else {
+            WAITING_COLOR
+        };
+    }
+    for mut color in brake_query.iter_mut() {
+        color.0 = if abilities.braking {
+            READY_COLOR
+        } else {
+            WAITING_COLOR
+        };
+    }
+}
@@ -1,7 +1,22 @@
 use bevy::prelude::*;
 use bevy_xpbd_3d::prelude::*;
 
-use crate::{AppState, SceneAssets};
+use crate::{despawn_all_recursive, AppState, GameLayer, SceneAssets};
+
+mod abilities;
+pub use abilities::*;
+
+mod catalogue;
+pub use catalogue::*;
+
+mod contact;
+pub use contact::*;
+
+mod damage;
+pub use damage::*;
+
+mod hud;
+use hud::*;
 
 mod systems;
 use systems::*;
@@ -10,6 +25,12 @@ use systems::*;
 #[derive(Component)]
 pub struct Cheese;
 
+// The cheese's velocity before the latest physics step, since collisions have already
+// changed `LinearVelocity` by the time they are reported
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component)]
+pub struct PreviousVelocity(pub Vec3);
+
 impl Cheese {
     // estimates at the size of the cheese wheel taken from
     // https://www.houseofcheese.co.uk/acatalog/A-Whole-Double-Gloucester-Cheese-25cm-dia-2310.html
@@ -35,39 +56,99 @@ impl Cheese {
             .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
     }
 
-    pub fn graphic(transform: Transform, scenes: &SceneAssets) -> SceneBundle {
-        SceneBundle {
-            // TODO: Why does this often fail to pull the correct asset
-            scene: scenes.cheese_good.clone(),
-            transform,
-            ..Default::default()
-        }
+    pub fn graphic(kind: CheeseKind, transform: Transform, scenes: &SceneAssets) -> impl Bundle {
+        (
+            CheeseTint(kind),
+            SceneBundle {
+                // TODO: Why does this often fail to pull the correct asset
+                scene: scenes.cheese_good.clone(),
+                transform: transform.with_scale(kind.stats().model_scale()),
+                ..Default::default()
+            },
+        )
     }
 
-    pub fn bundle(transform: Transform, scenes: &SceneAssets) -> impl Bundle {
+    pub fn bundle(kind: CheeseKind, transform: Transform) -> impl Bundle {
+        let stats = kind.stats();
         (
             Cheese,
+            kind,
             Name::new("Cheese"),
             RigidBody::Dynamic,
-            Self::collider(),
-            ColliderDensity(900.),
+            stats.collider(),
+            GameLayer::cheese(),
+            ColliderDensity(stats.density),
             Restitution {
                 coefficient: 0.0001,
                 combine_rule: CoefficientCombine::Min,
             },
-            Friction::new(0.5),
-            LinearDamping(0.08),
-            AngularDamping(0.08),
+            Friction::new(stats.friction),
+            LinearDamping(stats.linear_damping),
+            AngularDamping(stats.angular_damping),
             Dominance(1),
-            Self::graphic(transform, scenes),
+            (
+                PreviousVelocity::default(),
+                CheeseHealth::default(),
+                CheeseCondition::default(),
+                CheeseAbilities::default(),
+                CheeseContact::default(),
+            ),
+            SpatialBundle::from_transform(transform),
         )
     }
+
+    // the model is a child so that it can be scaled without scaling the collider
+    pub fn spawn(
+        kind: CheeseKind,
+        transform: Transform,
+        scenes: &SceneAssets,
+        commands: &mut Commands,
+    ) -> Entity {
+        commands
+            .spawn(Self::bundle(kind, transform))
+            .with_children(|builder| {
+                builder.spawn((
+                    Name::new("Cheese Model"),
+                    CheeseModel,
+                    Self::graphic(kind, Transform::default(), scenes),
+                ));
+            })
+            .id()
+    }
 }
 
 pub struct CheesePlugin;
 
 impl Plugin for CheesePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_inputs.run_if(in_state(AppState::Racing)));
+        app.init_resource::<SelectedCheese>()
+            .add_event::<CheeseTakeoff>()
+            .add_event::<CheeseLanding>()
+            .add_systems(
+                Update,
+                (
+                    (handle_inputs, handle_hop).chain(),
+                    (damage_cheese, update_cheese_condition, handle_brake).chain(),
+                    track_ability_hud,
+                )
+                    .run_if(in_state(AppState::Racing)),
+            )
+            // the menu shows off every kind of cheese, so this runs outside of races too
+            .add_systems(Update, tint_cheese_models)
+            .add_systems(OnEnter(AppState::SpawningScene), spawn_ability_hud)
+            .add_systems(
+                OnExit(AppState::Racing),
+                despawn_all_recursive::<AbilityHud>,
+            )
+            .add_systems(
+                PostUpdate,
+                track_previous_velocity.before(PhysicsSet::Prepare),
+            )
+            .add_systems(
+                PostUpdate,
+                update_cheese_contact
+                    .after(PhysicsSet::Sync)
+                    .run_if(in_state(AppState::Racing)),
+            );
     }
 }
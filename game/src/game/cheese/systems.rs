@@ -1,12 +1,18 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use bevy_xpbd_3d::prelude::*;
 
-use crate::Cheese;
+use crate::{
+    impact_speed, AppState, Cheese, CheeseAbilities, CheeseCondition, CheeseContact, CheeseHealth,
+    CheeseKind, CheeseLanding, CheeseModel, CheeseTakeoff, CheeseTint, GameLayer, GameOverReason,
+    Obstacle, Person, PreviousVelocity, SceneAssets,
+};
 
 pub(crate) fn handle_inputs(
     inputs: Res<Input<KeyCode>>,
     mut query: Query<
         (
+            &CheeseKind,
+            &CheeseContact,
             &LinearVelocity,
             &mut ExternalImpulse,
             &mut ExternalAngularImpulse,
@@ -27,11 +33,12 @@ pub(crate) fn handle_inputs(
     };
 
     if let Some(influence) = reference_frame_influence {
-        for (velocity, mut linear_impulse, mut angular_impulse) in query.iter_mut() {
+        for (kind, contact, velocity, mut linear_impulse, mut angular_impulse) in query.iter_mut() {
+            let influence = influence * kind.stats().steering * contact.control();
             // weight shift along velocity axis
             let spin_axis = velocity.0.normalize();
             let torque_impulse = influence * spin_axis;
-            let force_impulse = spin_axis.cross(Vec3::Y) * influence * 100.;
+            let force_impulse = spin_axis.cross(contact.normal) * influence * 100.;
             if force_impulse.is_finite() {
                 linear_impulse.set_impulse(force_impulse);
             }
@@ -41,3 +48,296 @@ pub(crate) fn handle_inputs(
         }
     }
 }
+
+// sweeps the cheese's collider a little way towards the ground to find what it is rolling on.
+// only the terrain and obstacles count as ground, not debris, ragdolls or pickups
+pub(crate) fn update_cheese_contact(
+    spatial_query: SpatialQuery,
+    mut query: Query<
+        (
+            Entity,
+            &Collider,
+            &Transform,
+            &CheeseAbilities,
+            &mut CheeseContact,
+        ),
+        With<Cheese>,
+    >,
+    mut takeoffs: EventWriter<CheeseTakeoff>,
+    mut landings: EventWriter<CheeseLanding>,
+    time: Res<Time>,
+) {
+    for (entity, collider, transform, abilities, mut contact) in query.iter_mut() {
+        // along the last ground normal, which is straight down while in the air
+        let hit = spatial_query.cast_shape(
+            collider,
+            transform.translation,
+            transform.rotation,
+            -contact.normal,
+            CheeseContact::MARGIN,
+            false,
+            SpatialQueryFilter::new()
+                .with_masks([GameLayer::Terrain, GameLayer::Obstacles])
+                .without_entities([entity]),
+        );
+        match (hit, contact.grounded) {
+            (Some(hit), was_grounded) => {
+                if !was_grounded {
+                    landings.send(CheeseLanding {
+                        cheese: entity,
+                        airtime: contact.airtime,
+                        hopped: contact.hopped,
+                    });
+                    contact.hopped = false;
+                } else if abilities.hop_cooldown.elapsed_secs() > CheeseContact::GRACE_TIME {
+                    // a hop that never got the cheese off the ground
+                    contact.hopped = false;
+                }
+                // a cast that starts out touching the ground may not find a normal
+                contact.normal = hit.normal1.try_normalize().unwrap_or(contact.normal);
+                contact.grounded = true;
+                contact.airtime = 0.;
+            }
+            (None, true) => {
+                takeoffs.send(CheeseTakeoff { cheese: entity });
+                contact.normal = Vec3::Y;
+                contact.grounded = false;
+            }
+            (None, false) => {
+                contact.airtime += time.delta_seconds();
+            }
+        }
+    }
+}
+
+pub(crate) fn handle_hop(
+    inputs: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            &Mass,
+            &mut CheeseContact,
+            &mut CheeseAbilities,
+            &mut ExternalImpulse,
+        ),
+        With<Cheese>,
+    >,
+) {
+    let wants_hop = inputs.any_just_pressed([KeyCode::Up, KeyCode::W]);
+    for (mass, mut contact, mut abilities, mut impulse) in query.iter_mut() {
+        abilities.hop_cooldown.tick(time.delta());
+        if wants_hop && contact.on_ground() && abilities.can_hop() {
+            abilities.hop();
+            contact.hopped = true;
+            // added on top of any steering impulse from this frame
+            impulse.apply_impulse(Vec3::Y * CheeseAbilities::HOP_SPEED * mass.0);
+        }
+    }
+}
+
+// the brake is reapplied every frame so that it stays on top of changes from damage
+pub(crate) fn handle_brake(
+    inputs: Res<Input<KeyCode>>,
+    mut query: Query<
+        (
+            &CheeseKind,
+            &CheeseCondition,
+            &mut CheeseAbilities,
+            &mut Friction,
+            &mut AngularDamping,
+        ),
+        With<Cheese>,
+    >,
+) {
+    let braking = inputs.any_pressed([KeyCode::Down, KeyCode::S]);
+    for (kind, condition, mut abilities, mut friction, mut angular_damping) in query.iter_mut() {
+        if abilities.braking != braking {
+            abilities.braking = braking;
+        }
+        let stats = kind.stats();
+        let next_friction = abilities.friction(&stats);
+        if *friction != next_friction {
+            *friction = next_friction;
+        }
+        let next_angular_damping = abilities.angular_damping(&stats, condition);
+        if *angular_damping != next_angular_damping {
+            *angular_damping = next_angular_damping;
+        }
+    }
+}
+
+pub(crate) fn track_previous_velocity(
+    mut query: Query<(&LinearVelocity, &mut PreviousVelocity), With<Cheese>>,
+) {
+    for (velocity, mut previous_velocity) in query.iter_mut() {
+        previous_velocity.0 = velocity.0;
+    }
+}
+
+// hard hits on obstacles and ragdolls knock health off the cheese
+#[allow(clippy::type_complexity)]
+pub(crate) fn damage_cheese(
+    mut collisions: EventReader<CollisionStarted>,
+    contacts: Res<Collisions>,
+    mut cheese_query: Query<
+        (
+            Entity,
+            &CheeseKind,
+            &PreviousVelocity,
+            &Rotation,
+            &mut CheeseHealth,
+        ),
+        With<Cheese>,
+    >,
+    parent_query: Query<&Parent>,
+    hazard_query: Query<(), Or<(With<Obstacle>, With<Person>)>>,
+    body_query: Query<(&RigidBody, &LinearVelocity), Without<Cheese>>,
+) {
+    let Ok((cheese, kind, previous_velocity, rotation, mut health)) = cheese_query.get_single_mut()
+    else {
+        return;
+    };
+    // the cheese is dominant, so nothing it hits changes its velocity. the damage comes from how
+    // hard it ran into the other body instead
+    let mut hardest_hit: f32 = 0.;
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let other = if *entity1 == cheese {
+            *entity2
+        } else if *entity2 == cheese {
+            *entity1
+        } else {
+            continue;
+        };
+        // obstacle parts and ragdoll limbs are children of the entity that marks them
+        let root = parent_query.get(other).map_or(other, |parent| parent.get());
+        if !hazard_query.contains(other) && !hazard_query.contains(root) {
+            continue;
+        }
+        let Some(contacts) = contacts.get(cheese, other) else {
+            continue;
+        };
+        // moving obstacles are kinematic and keep their velocity through the contact, but
+        // ragdolls have already been shoved aside by now, so they count as standing still
+        let body = if body_query.contains(other) {
+            other
+        } else {
+            root
+        };
+        let other_velocity = match body_query.get(body) {
+            Ok((rigid_body, velocity)) if !rigid_body.is_dynamic() => velocity.0,
+            _ => Vec3::ZERO,
+        };
+        let speed = impact_speed(
+            contacts,
+            cheese,
+            rotation,
+            previous_velocity.0 - other_velocity,
+        );
+        hardest_hit = hardest_hit.max(speed);
+    }
+    let damage = CheeseHealth::damage(hardest_hit, &kind.stats());
+    if damage > 0. {
+        health.0 -= damage;
+    }
+}
+
+// gives the meshes of each newly spawned cheese model a copy of their material in the kind's tint
+#[allow(clippy::type_complexity)]
+pub(crate) fn tint_cheese_models(
+    mut mesh_query: Query<(Entity, &mut Handle<StandardMaterial>), Added<Handle<StandardMaterial>>>,
+    tint_query: Query<&CheeseTint>,
+    parent_query: Query<&Parent>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut tinted: Local<HashMap<(AssetId<StandardMaterial>, CheeseKind), Handle<StandardMaterial>>>,
+) {
+    for (entity, mut material) in mesh_query.iter_mut() {
+        let Some(tint) = parent_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| tint_query.get(ancestor).ok())
+        else {
+            continue;
+        };
+        let key = (material.id(), tint.0);
+        if let Some(handle) = tinted.get(&key) {
+            *material = handle.clone();
+            continue;
+        }
+        let Some(mut tinted_material) = materials.get(material.id()).cloned() else {
+            continue;
+        };
+        let base_color = tinted_material.base_color.as_rgba_f32();
+        let tint = tint.0.tint().as_rgba_f32();
+        tinted_material.base_color = Color::rgba(
+            base_color[0] * tint[0],
+            base_color[1] * tint[1],
+            base_color[2] * tint[2],
+            base_color[3],
+        );
+        let handle = materials.add(tinted_material);
+        tinted.insert(key, handle.clone());
+        *material = handle;
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn update_cheese_condition(
+    mut commands: Commands,
+    mut cheese_query: Query<
+        (
+            Entity,
+            &CheeseKind,
+            &CheeseHealth,
+            &mut CheeseCondition,
+            &Transform,
+            &LinearVelocity,
+            &mut Visibility,
+        ),
+        (With<Cheese>, Changed<CheeseHealth>),
+    >,
+    mut model_query: Query<
+        (&mut Handle<Scene>, &mut Transform),
+        (With<CheeseModel>, Without<Cheese>),
+    >,
+    children_query: Query<&Children>,
+    scenes: Res<SceneAssets>,
+    mut state: ResMut<NextState<AppState>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, kind, health, mut condition, transform, velocity, mut visibility) in
+        cheese_query.iter_mut()
+    {
+        let next_condition = CheeseCondition::from_health(health);
+        if next_condition <= *condition {
+            continue;
+        }
+        let stats = kind.stats();
+        next_condition.spawn_chunks(
+            transform,
+            velocity.0,
+            &stats,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+        *condition = next_condition;
+
+        if next_condition == CheeseCondition::Broken {
+            info!("The cheese broke!");
+            *visibility = Visibility::Hidden;
+            commands.insert_resource(GameOverReason::CheeseDestroyed);
+            state.set(AppState::GameOver);
+            continue;
+        }
+        commands.entity(entity).insert((
+            next_condition.collider(&stats),
+            next_condition.angular_damping(&stats),
+        ));
+        for child in children_query.iter_descendants(entity) {
+            if let Ok((mut scene, mut model_transform)) = model_query.get_mut(child) {
+                *scene = next_condition.model(&scenes);
+                model_transform.scale = stats.model_scale() * next_condition.scale();
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::path::Path;
+
+use derive_more::From;
+use serde::{Deserialize, Serialize};
+
+use bevy::prelude::*;
+
+use ::noise::NoiseFn;
+
+use crate::{
+    Chunk, HeightModifier, Level, Obstacle, ObstacleKind, TerrainNoise, TextureAssets, Vertex,
+};
+
+// A hand-made course: a terrain seed, the pieces placed on it, and any painted terrain edits.
+// Courses are stored on disk as RON documents, see `Course::load` and `Course::save`.
+#[derive(Clone, Debug)]
+#[derive(Resource, Serialize, Deserialize)]
+pub struct Course {
+    pub version: u32,
+    pub seed: u32,
+    pub pieces: Vec<CoursePiece>,
+    pub height_modifiers: Vec<HeightModifier>,
+}
+
+impl Course {
+    pub const VERSION: u32 = 2;
+    pub const DEFAULT_PATH: &'static str = "courses/editor.course";
+    // where the editor saves instead when the default course is there but cannot be loaded
+    pub const BACKUP_PATH: &'static str = "courses/editor.course.bak";
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            version: Self::VERSION,
+            seed,
+            pieces: vec![],
+            height_modifiers: vec![],
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CourseError> {
+        let contents = std::fs::read_to_string(path)?;
+        let header: CourseHeader = ron::from_str(&contents)?;
+        match header.version {
+            1 => Ok(ron::from_str::<CourseV1>(&contents)?.upgrade()),
+            Self::VERSION => Ok(ron::from_str(&contents)?),
+            version => Err(CourseError::UnsupportedVersion(version)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CourseError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn terrain_noise(&self) -> TerrainNoise {
+        TerrainNoise::with_modifiers(self.seed, self.height_modifiers.clone())
+    }
+
+    // the pieces whose anchor falls within the level chunk at `origin`
+    pub fn pieces_in_chunk<'a>(
+        &'a self,
+        origin: Vertex,
+        level: &'a Level,
+    ) -> impl Iterator<Item = &'a CoursePiece> + 'a {
+        self.pieces.iter().filter(move |piece| {
+            let chunk = Chunk::from_translation(
+                piece.translation(level.quad_size),
+                level.chunk_size,
+                level.quad_size,
+            );
+            chunk.origin == origin
+        })
+    }
+
+    // the index of the piece closest to `vertex`, if any is within `max_distance` vertices
+    pub fn nearest_piece(&self, vertex: Vertex, max_distance: f32) -> Option<usize> {
+        self.pieces
+            .iter()
+            .enumerate()
+            .map(|(index, piece)| (index, piece.distance_to(vertex)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct CoursePiece {
+    pub kind: ObstacleKind,
+    // in global grid units, the same coordinates that generated walls are placed with
+    pub vertex: Vertex,
+}
+
+impl CoursePiece {
+    pub fn new(kind: ObstacleKind, vertex: Vertex) -> Self {
+        Self { kind, vertex }
+    }
+
+    pub fn translation(&self, quad_size: Vec2) -> Vec3 {
+        Vec3::new(
+            self.vertex.x as f32 * quad_size.x,
+            0.,
+            self.vertex.z as f32 * quad_size.y,
+        )
+    }
+
+    pub fn distance_to(&self, vertex: Vertex) -> f32 {
+        // measure from the closest point along the piece's extent
+        let nearest_x = vertex
+            .x
+            .clamp(self.vertex.x, self.vertex.x + self.kind.extent());
+        let dx = vertex.x - nearest_x;
+        let dz = vertex.z - self.vertex.z;
+        Vec2::new(dx as f32, dz as f32).length()
+    }
+
+    pub fn spawn(
+        &self,
+        level: &Level,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        textures: &TextureAssets,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        Obstacle::new(self.kind, self.vertex).spawn(
+            level,
+            terrain_noise,
+            commands,
+            textures,
+            meshes,
+            materials,
+        )
+    }
+}
+
+// Just enough of a course file to tell which version it was saved with
+#[derive(Deserialize)]
+struct CourseHeader {
+    version: u32,
+}
+
+// Courses saved before walls and props became obstacle kinds
+#[derive(Deserialize)]
+struct CourseV1 {
+    seed: u32,
+    pieces: Vec<CoursePieceV1>,
+    height_modifiers: Vec<HeightModifier>,
+}
+
+#[derive(Deserialize)]
+struct CoursePieceV1 {
+    kind: PieceKindV1,
+    vertex: Vertex,
+}
+
+#[derive(Deserialize)]
+enum PieceKindV1 {
+    Wall { width: i32 },
+    Prop(PropKindV1),
+}
+
+#[derive(Deserialize)]
+enum PropKindV1 {
+    Post,
+    Pillar,
+}
+
+impl CourseV1 {
+    fn upgrade(self) -> Course {
+        Course {
+            pieces: self
+                .pieces
+                .into_iter()
+                .map(|piece| {
+                    let kind = match piece.kind {
+                        PieceKindV1::Wall { width } => ObstacleKind::Wall { width },
+                        PieceKindV1::Prop(PropKindV1::Post) => ObstacleKind::Post,
+                        PieceKindV1::Prop(PropKindV1::Pillar) => ObstacleKind::Pillar,
+                    };
+                    CoursePiece::new(kind, piece.vertex)
+                })
+                .collect(),
+            height_modifiers: self.height_modifiers,
+            ..Course::new(self.seed)
+        }
+    }
+}
+
+#[derive(Debug, From)]
+pub enum CourseError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    #[from(ignore)]
+    UnsupportedVersion(u32),
+}
+
+impl CourseError {
+    // whether the course file just isn't there yet
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, CourseError::Io(error) if error.kind() == std::io::ErrorKind::NotFound)
+    }
+}
+
+impl std::fmt::Display for CourseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CourseError::Io(error) => write!(f, "could not access course file: {}", error),
+            CourseError::Parse(error) => write!(f, "could not parse course file: {}", error),
+            CourseError::Serialize(error) => write!(f, "could not write course file: {}", error),
+            CourseError::UnsupportedVersion(version) => {
+                write!(f, "unsupported course version {}", version)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CourseError {}
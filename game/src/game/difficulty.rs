@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+// The named difficulty curves that can be picked from the menu
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    // the preset after this one, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|difficulty| difficulty == self);
+        Self::ALL[(index.unwrap_or_default() + 1) % Self::ALL.len()]
+    }
+}
+
+// How hard the race is at some distance down the hill
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub struct DifficultySettings {
+    // obstacle noise must exceed this for an obstacle to be placed, so lower is denser
+    pub obstacle_threshold: f64,
+    // scales the registry weight of obstacles that move
+    pub moving_obstacle_weight: f32,
+    // how many ragdolls drop either side of the middle of a burst
+    pub burst_size: i32,
+    // seconds between bursts of ragdolls
+    pub spawn_interval: f32,
+    // how strongly ragdolls are pulled towards the cheese
+    pub pull_strength: f32,
+}
+
+// the settings the game used before difficulty could be chosen
+impl Default for DifficultySettings {
+    fn default() -> Self {
+        Self {
+            obstacle_threshold: 0.995,
+            moving_obstacle_weight: 1.,
+            burst_size: 4,
+            spawn_interval: 2.,
+            pull_strength: 1.2e5,
+        }
+    }
+}
+
+impl DifficultySettings {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            obstacle_threshold: self.obstacle_threshold
+                + (other.obstacle_threshold - self.obstacle_threshold) * t as f64,
+            moving_obstacle_weight: lerp(self.moving_obstacle_weight, other.moving_obstacle_weight),
+            burst_size: lerp(self.burst_size as f32, other.burst_size as f32).round() as i32,
+            spawn_interval: lerp(self.spawn_interval, other.spawn_interval),
+            pull_strength: lerp(self.pull_strength, other.pull_strength),
+        }
+    }
+}
+
+// Maps the distance down the hill (in world units) to `DifficultySettings`, interpolating
+// linearly between keyframes and holding the last keyframe forever. A curve without any
+// keyframes always gives the default settings
+#[derive(Clone, Debug)]
+#[derive(Resource, Reflect)]
+pub struct DifficultyCurve {
+    pub preset: Option<Difficulty>,
+    // sorted by distance
+    pub keyframes: Vec<(f32, DifficultySettings)>,
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        Self::preset(Difficulty::default())
+    }
+}
+
+impl DifficultyCurve {
+    pub fn new(mut keyframes: Vec<(f32, DifficultySettings)>) -> Self {
+        keyframes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self {
+            preset: None,
+            keyframes,
+        }
+    }
+
+    pub fn preset(difficulty: Difficulty) -> Self {
+        let pull_strength = match difficulty {
+            Difficulty::Easy => 0.9e5,
+            Difficulty::Normal => 1.2e5,
+            Difficulty::Hard => 1.5e5,
+        };
+        let settings = |obstacle_threshold, moving_obstacle_weight, burst_size, spawn_interval| {
+            DifficultySettings {
+                obstacle_threshold,
+                moving_obstacle_weight,
+                burst_size,
+                spawn_interval,
+                pull_strength,
+            }
+        };
+        let mut curve = Self::new(match difficulty {
+            Difficulty::Easy => vec![
+                (0., settings(0.997, 0.5, 2, 3.)),
+                (800., settings(0.99, 0.75, 3, 2.5)),
+                (4000., settings(0.985, 1., 3, 2.)),
+            ],
+            // starts out with the values the game used before difficulty could be chosen
+            Difficulty::Normal => vec![
+                (0., settings(0.995, 1., 4, 2.)),
+                (320., settings(0.995, 1., 4, 2.)),
+                (400., settings(0.98, 1., 4, 2.)),
+                (4000., settings(0.97, 1.5, 5, 1.6)),
+            ],
+            Difficulty::Hard => vec![
+                (0., settings(0.99, 1.5, 5, 1.5)),
+                (400., settings(0.975, 2., 5, 1.5)),
+                (3000., settings(0.96, 3., 6, 1.)),
+            ],
+        });
+        curve.preset = Some(difficulty);
+        curve
+    }
+
+    pub fn sample(&self, distance: f32) -> DifficultySettings {
+        let next = self
+            .keyframes
+            .iter()
+            .position(|(keyframe_distance, _)| *keyframe_distance > distance);
+        match next {
+            Some(0) => self.keyframes[0].1,
+            Some(index) => {
+                let (start, from) = self.keyframes[index - 1];
+                let (end, to) = self.keyframes[index];
+                from.lerp(&to, (distance - start) / (end - start))
+            }
+            None => self
+                .keyframes
+                .last()
+                .map_or_else(DifficultySettings::default, |(_, settings)| *settings),
+        }
+    }
+}
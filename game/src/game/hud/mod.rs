@@ -0,0 +1,291 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{
+    despawn_all_recursive, format_race_time, AppState, Cheese, CheeseGrip, Person, PlayerCamera,
+    RaceClock, RunStats, Score, StyleEvent, StyleScore, RACE_START_Z,
+};
+
+mod widgets;
+pub use widgets::*;
+
+// The root of the race HUD, which is only shown while racing
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct RaceHud;
+
+// pursuers behind the cheese and closer than this get an arrow once they are off-screen
+const PURSUER_WARNING_DISTANCE: f32 = 30.;
+// how far the arrow stays from the edge of the screen
+const ARROW_MARGIN: f32 = 16.;
+
+pub struct RaceHudPlugin;
+
+impl Plugin for RaceHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::SpawningScene), spawn_race_hud)
+            .add_systems(OnEnter(AppState::Racing), show_race_hud)
+            .add_systems(OnExit(AppState::Racing), hide_race_hud)
+            .add_systems(
+                Update,
+                (
+                    track_hud_stats,
+                    track_pursuer_arrow,
+                    spawn_style_popups,
+                    animate_style_popups,
+                    track_grip_meter,
+                )
+                    .run_if(in_state(AppState::Racing)),
+            )
+            .add_systems(OnExit(AppState::GameOver), despawn_all_recursive::<RaceHud>);
+    }
+}
+
+fn spawn_race_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Race HUD"),
+            RaceHud,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        right: Val::Percent(1.),
+                        top: Val::Percent(1.),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::FlexEnd,
+                        row_gap: Val::Px(8.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|builder| {
+                    spawn_stat_widget(builder, HudStat::Score, 64.);
+                    spawn_stat_widget(builder, HudStat::Distance, 32.);
+                    spawn_stat_widget(builder, HudStat::Combo, 32.);
+                    spawn_stat_widget(builder, HudStat::Speed, 32.);
+                    spawn_stat_widget(builder, HudStat::Time, 32.);
+                    spawn_stat_widget(builder, HudStat::NearestPursuer, 32.);
+                    spawn_stat_widget(builder, HudStat::Knockdowns, 32.);
+                });
+            spawn_pursuer_arrow(builder);
+            spawn_style_popup_column(builder);
+            spawn_grip_meter(builder);
+        });
+}
+
+fn show_race_hud(mut query: Query<&mut Visibility, With<RaceHud>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn hide_race_hud(mut query: Query<&mut Visibility, With<RaceHud>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn track_hud_stats(
+    mut text_query: Query<(&HudStatValue, &mut Text)>,
+    cheese_query: Query<(&Transform, &LinearVelocity), With<Cheese>>,
+    person_query: Query<&Transform, (With<Person>, Without<Cheese>)>,
+    score: Res<Score>,
+    style: Res<StyleScore>,
+    stats: Res<RunStats>,
+    clock: Res<RaceClock>,
+) {
+    let Ok((cheese_transform, velocity)) = cheese_query.get_single() else {
+        return;
+    };
+    let nearest_pursuer = person_query
+        .iter()
+        .map(|transform| transform.translation.distance(cheese_transform.translation))
+        .filter(|distance| distance.is_finite())
+        .min_by(f32::total_cmp);
+    for (stat, mut text) in text_query.iter_mut() {
+        let value = match stat.0 {
+            HudStat::Score => format!("{:.0}", score.0),
+            HudStat::Distance => format!(
+                "{:.0} m",
+                (cheese_transform.translation.z - RACE_START_Z).max(0.)
+            ),
+            HudStat::Combo => {
+                if style.combo > 1 {
+                    format!("x{}", style.multiplier())
+                } else {
+                    "-".to_string()
+                }
+            }
+            // meters per second to kilometers per hour
+            HudStat::Speed => format!("{:.0} km/h", velocity.0.length() * 3.6),
+            HudStat::Time => format_race_time(clock.0),
+            HudStat::NearestPursuer => nearest_pursuer
+                .map(|distance| format!("{:.0} m", distance))
+                .unwrap_or_else(|| "-".to_string()),
+            HudStat::Knockdowns => stats.knockdowns.to_string(),
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn track_pursuer_arrow(
+    mut arrow_query: Query<(&mut Style, &mut Visibility), With<PursuerArrow>>,
+    mut glyph_query: Query<&mut Transform, With<PursuerArrowGlyph>>,
+    mut text_query: Query<&mut Text, With<PursuerArrowText>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    cheese_query: Query<&Transform, (With<Cheese>, Without<PursuerArrowGlyph>)>,
+    person_query: Query<&Transform, (With<Person>, Without<Cheese>, Without<PursuerArrowGlyph>)>,
+) {
+    let Ok((mut style, mut visibility)) = arrow_query.get_single_mut() else {
+        return;
+    };
+    let (Ok((camera, camera_transform)), Ok(cheese_transform)) =
+        (camera_query.get_single(), cheese_query.get_single())
+    else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let cheese = cheese_transform.translation;
+    // the closest pursuer coming down the hill behind the cheese that the camera can't see
+    let pursuer = person_query
+        .iter()
+        .map(|transform| transform.translation)
+        .filter(|position| position.is_finite() && position.z < cheese.z)
+        .filter(|position| position.distance(cheese) < PURSUER_WARNING_DISTANCE)
+        .filter(|position| {
+            !camera
+                .world_to_viewport(camera_transform, *position)
+                .is_some_and(|point| {
+                    point.cmpge(Vec2::ZERO).all() && point.cmple(viewport_size).all()
+                })
+        })
+        .min_by(|a, b| a.distance(cheese).total_cmp(&b.distance(cheese)));
+    let Some(pursuer) = pursuer else {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+
+    // the direction on screen, where y points down. anything behind the camera is drawn
+    // along the bottom, since that's the way back up the hill
+    let local = camera_transform
+        .affine()
+        .inverse()
+        .transform_point3(pursuer);
+    let mut direction = Vec2::new(local.x, -local.y);
+    if local.z > 0. {
+        direction.y = direction.y.abs().max(1.);
+    }
+    let direction = direction.try_normalize().unwrap_or(Vec2::Y);
+    let half_size = viewport_size / 2. - Vec2::splat(ARROW_MARGIN + PursuerArrow::SIZE / 2.);
+    let reach = (half_size.x / direction.x.abs()).min(half_size.y / direction.y.abs());
+    let position = viewport_size / 2. + direction * reach - Vec2::splat(PursuerArrow::SIZE / 2.);
+    style.left = Val::Px(position.x);
+    style.top = Val::Px(position.y);
+    *visibility = Visibility::Inherited;
+
+    // the glyph points up, and UI rotations turn clockwise on screen
+    for mut transform in glyph_query.iter_mut() {
+        transform.rotation = Quat::from_rotation_z(direction.x.atan2(-direction.y));
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("{:.0} m", pursuer.distance(cheese));
+    }
+}
+
+fn spawn_style_popups(
+    mut commands: Commands,
+    mut events: EventReader<StyleEvent>,
+    column_query: Query<Entity, With<StylePopupColumn>>,
+) {
+    let Ok(column) = column_query.get_single() else {
+        return;
+    };
+    for event in events.read() {
+        let text = if event.multiplier > 1 {
+            format!(
+                "{} +{:.0} (x{})",
+                event.source.label(),
+                event.points,
+                event.multiplier
+            )
+        } else {
+            format!("{} +{:.0}", event.source.label(), event.points)
+        };
+        commands.entity(column).with_children(|builder| {
+            builder.spawn(style_popup(text));
+        });
+    }
+}
+
+fn animate_style_popups(
+    mut commands: Commands,
+    mut popup_query: Query<(Entity, &mut StylePopup, &mut Style, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut popup, mut style, mut text) in popup_query.iter_mut() {
+        if popup.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = popup.0.percent();
+        style.bottom = Val::Px(StylePopup::RISE * progress);
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(1. - progress);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn track_grip_meter(
+    mut meter_query: Query<&mut Style, With<GripMeter>>,
+    mut escape_query: Query<&mut Style, (With<GripEscapeBar>, Without<GripMeter>)>,
+    mut timer_query: Query<
+        &mut Style,
+        (
+            With<GripTimerBar>,
+            Without<GripMeter>,
+            Without<GripEscapeBar>,
+        ),
+    >,
+    grip: Res<CheeseGrip>,
+) {
+    let display = if grip.is_held() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in meter_query.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    if !grip.is_held() {
+        return;
+    }
+    for mut style in escape_query.iter_mut() {
+        style.width = Val::Percent(grip.escape_fraction() * 100.);
+    }
+    for mut style in timer_query.iter_mut() {
+        style.width = Val::Percent(grip.timer.percent_left() * 100.);
+    }
+}
@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+
+// The readouts that a stat widget can show
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Component)]
+pub enum HudStat {
+    Score,
+    Distance,
+    Combo,
+    Speed,
+    Time,
+    NearestPursuer,
+    // pursuers knocked down so far
+    Knockdowns,
+}
+
+impl HudStat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HudStat::Score => "Score",
+            HudStat::Distance => "Distance",
+            HudStat::Combo => "Combo",
+            HudStat::Speed => "Speed",
+            HudStat::Time => "Time",
+            HudStat::NearestPursuer => "Nearest",
+            HudStat::Knockdowns => "Flattened",
+        }
+    }
+}
+
+// Marks the text that holds a stat's value, on the widget's value node
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct HudStatValue(pub HudStat);
+
+// Points at a pursuer from the edge of the screen
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct PursuerArrow;
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct PursuerArrowGlyph;
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct PursuerArrowText;
+
+impl PursuerArrow {
+    pub const SIZE: f32 = 48.;
+}
+
+// Text that drifts up and fades out after a trick
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct StylePopup(pub Timer);
+// Holds the popups, stacked in the middle of the screen
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct StylePopupColumn;
+
+impl StylePopup {
+    pub const LIFETIME: f32 = 1.2;
+    // how far the popup drifts up over its lifetime
+    pub const RISE: f32 = 48.;
+}
+
+// Shows how close the player is to escaping a grab, while the cheese is held
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct GripMeter;
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct GripEscapeBar;
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct GripTimerBar;
+
+const LABEL_COLOR: Color = Color::rgba(1., 1., 1., 0.7);
+const WARNING_COLOR: Color = Color::rgb(0.9, 0.1, 0.1);
+
+// a label with its value underneath, lined up along the right edge
+pub fn spawn_stat_widget(builder: &mut ChildBuilder, stat: HudStat, font_size: f32) {
+    builder
+        .spawn((
+            Name::new(format!("{} Widget", stat.label())),
+            stat,
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                stat.label(),
+                TextStyle {
+                    font_size: 20.,
+                    color: LABEL_COLOR,
+                    ..Default::default()
+                },
+            ));
+            builder.spawn((
+                HudStatValue(stat),
+                TextBundle::from_section(
+                    "-",
+                    TextStyle {
+                        font_size,
+                        ..Default::default()
+                    },
+                ),
+            ));
+        });
+}
+
+// the glyph is turned to face the pursuer, with the distance to it written underneath
+pub fn spawn_pursuer_arrow(builder: &mut ChildBuilder) {
+    builder
+        .spawn((
+            Name::new("Pursuer Arrow"),
+            PursuerArrow,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(PursuerArrow::SIZE),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                PursuerArrowGlyph,
+                TextBundle::from_section(
+                    "^",
+                    TextStyle {
+                        font_size: PursuerArrow::SIZE,
+                        color: WARNING_COLOR,
+                        ..Default::default()
+                    },
+                ),
+            ));
+            builder.spawn((
+                PursuerArrowText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 20.,
+                        color: WARNING_COLOR,
+                        ..Default::default()
+                    },
+                ),
+            ));
+        });
+}
+
+pub fn spawn_style_popup_column(builder: &mut ChildBuilder) {
+    builder.spawn((
+        Name::new("Style Popups"),
+        StylePopupColumn,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(25.),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+}
+
+pub fn style_popup(text: String) -> impl Bundle {
+    (
+        Name::new("Style Popup"),
+        StylePopup(Timer::from_seconds(StylePopup::LIFETIME, TimerMode::Once)),
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 36.,
+                color: Color::rgb(0.96, 0.76, 0.32),
+                ..Default::default()
+            },
+        ),
+    )
+}
+
+fn meter_bar(builder: &mut ChildBuilder, marker: impl Bundle, color: Color) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(320.),
+                height: Val::Px(16.),
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.1, 0.1, 0.1, 0.6).into(),
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn((
+                marker,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: color.into(),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+// a prompt with a bar for the escape and one for the time left to make it
+pub fn spawn_grip_meter(builder: &mut ChildBuilder) {
+    builder
+        .spawn((
+            Name::new("Grip Meter"),
+            GripMeter,
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    bottom: Val::Percent(20.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                "Grabbed! Mash Left and Right!",
+                TextStyle {
+                    font_size: 36.,
+                    color: WARNING_COLOR,
+                    ..Default::default()
+                },
+            ));
+            meter_bar(builder, GripEscapeBar, Color::rgb(0.96, 0.76, 0.32));
+            meter_bar(builder, GripTimerBar, WARNING_COLOR);
+        });
+}
@@ -0,0 +1,103 @@
+use bevy_xpbd_3d::prelude::*;
+
+// What each collider is, so that pairs that never need to touch can skip their contacts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(PhysicsLayer)]
+pub enum GameLayer {
+    Cheese,
+    Terrain,
+    Obstacles,
+    // the pieces that obstacles and the cheese break into, which the cheese doesn't roll on
+    Debris,
+    // ragdoll torsos
+    RagdollBodies,
+    // ragdoll heads, arms, hands and legs
+    RagdollLimbs,
+    // pickups that only the cheese can touch
+    Sensors,
+}
+
+impl GameLayer {
+    pub fn cheese() -> CollisionLayers {
+        CollisionLayers::new(
+            [GameLayer::Cheese],
+            [
+                GameLayer::Terrain,
+                GameLayer::Obstacles,
+                GameLayer::Debris,
+                GameLayer::RagdollBodies,
+                GameLayer::RagdollLimbs,
+                GameLayer::Sensors,
+            ],
+        )
+    }
+
+    pub fn terrain() -> CollisionLayers {
+        CollisionLayers::new(
+            [GameLayer::Terrain],
+            [
+                GameLayer::Cheese,
+                GameLayer::Obstacles,
+                GameLayer::Debris,
+                GameLayer::RagdollBodies,
+                GameLayer::RagdollLimbs,
+            ],
+        )
+    }
+
+    pub fn obstacles() -> CollisionLayers {
+        Self::solid(GameLayer::Obstacles)
+    }
+
+    pub fn debris() -> CollisionLayers {
+        Self::solid(GameLayer::Debris)
+    }
+
+    fn solid(layer: GameLayer) -> CollisionLayers {
+        CollisionLayers::new(
+            [layer],
+            [
+                GameLayer::Cheese,
+                GameLayer::Terrain,
+                GameLayer::Obstacles,
+                GameLayer::Debris,
+                GameLayer::RagdollBodies,
+                GameLayer::RagdollLimbs,
+            ],
+        )
+    }
+
+    // torsos bump into other ragdolls, unless `ragdoll_contacts` is off
+    pub fn ragdoll_body(ragdoll_contacts: bool) -> CollisionLayers {
+        Self::ragdoll_part(GameLayer::RagdollBodies, ragdoll_contacts)
+    }
+
+    // limbs bump into other ragdolls, unless `ragdoll_contacts` is off. they are held on by
+    // joints, so contacts with the rest of their own ragdoll are dropped after the narrow phase
+    pub fn ragdoll_limb(ragdoll_contacts: bool) -> CollisionLayers {
+        Self::ragdoll_part(GameLayer::RagdollLimbs, ragdoll_contacts)
+    }
+
+    fn ragdoll_part(layer: GameLayer, ragdoll_contacts: bool) -> CollisionLayers {
+        let layers = CollisionLayers::new(
+            [layer],
+            [
+                GameLayer::Cheese,
+                GameLayer::Terrain,
+                GameLayer::Obstacles,
+                GameLayer::Debris,
+            ],
+        );
+        if ragdoll_contacts {
+            layers
+                .add_mask(GameLayer::RagdollBodies)
+                .add_mask(GameLayer::RagdollLimbs)
+        } else {
+            layers
+        }
+    }
+
+    pub fn sensor() -> CollisionLayers {
+        CollisionLayers::new([GameLayer::Sensors], [GameLayer::Cheese])
+    }
+}
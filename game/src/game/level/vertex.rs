@@ -1,10 +1,11 @@
 use derive_more::{Add, AddAssign, From, Mul, MulAssign, Sub, SubAssign};
+use serde::{Deserialize, Serialize};
 
 use bevy::prelude::*;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[derive(Add, AddAssign, Mul, MulAssign, Sub, SubAssign, From)]
-#[derive(Reflect)]
+#[derive(Reflect, Serialize, Deserialize)]
 pub struct Vertex {
     pub x: i32,
     pub z: i32,
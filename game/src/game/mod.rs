@@ -9,6 +9,18 @@ pub use camera::*;
 mod cheese;
 pub use cheese::*;
 
+mod course;
+pub use course::*;
+
+mod difficulty;
+pub use difficulty::*;
+
+mod hud;
+pub use hud::*;
+
+mod layers;
+pub use layers::*;
+
 mod level;
 pub use level::*;
 
@@ -18,6 +30,12 @@ pub use obstacles::*;
 mod person;
 pub use person::*;
 
+mod powerups;
+pub use powerups::*;
+
+mod race;
+pub use race::*;
+
 mod score;
 pub use score::*;
 
@@ -29,6 +47,7 @@ pub struct CheeseRacePlugin;
 impl Plugin for CheeseRacePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<AppState>()
+            .init_resource::<DifficultyCurve>()
             .add_plugins(PhysicsPlugins::default())
             .configure_sets(
                 PostUpdate,
@@ -37,8 +56,21 @@ impl Plugin for CheeseRacePlugin {
                     PhysicsSet::StepSimulation,
                     PhysicsSet::Sync,
                 )
-                    .run_if(in_state(AppState::Racing).or_else(in_state(AppState::SpawningScene))),
+                    .run_if(
+                        in_state(AppState::Racing)
+                            .or_else(in_state(AppState::SpawningScene))
+                            // the editor relies on colliders for picking
+                            .or_else(in_state(AppState::Editor)),
+                    ),
             )
-            .add_plugins((LevelPlugin, PersonPlugin, CheesePlugin, ScorePlugin));
+            .add_plugins((
+                LevelPlugin,
+                PersonPlugin,
+                CheesePlugin,
+                RacePlugin,
+                ScorePlugin,
+                RaceHudPlugin,
+                PowerUpsPlugin,
+            ));
     }
 }
@@ -0,0 +1,116 @@
+use ::noise::NoiseFn;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{GameLayer, Level, TerrainChunk, TextureAssets};
+
+use super::Obstacle;
+
+// A piece of a broken obstacle, which is cleaned up once its timer runs out
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct Debris(pub Timer);
+
+impl Debris {
+    const LIFETIME: f32 = 8.;
+    // light enough that the cheese and ragdolls can knock it around
+    const DENSITY: f32 = 200.;
+    // how much of the cheese's momentum is passed on to the debris
+    const CARRIED_VELOCITY: f32 = 0.5;
+    const SCATTER_SPEED: f32 = 4.;
+
+    pub fn new() -> Self {
+        Self(Timer::from_seconds(Self::LIFETIME, TimerMode::Once))
+    }
+
+    // replaces an obstacle with its fragments, flung along with the cheese that hit it.
+    // fragments that would start underground are left out.
+    #[allow(clippy::too_many_arguments)]
+    pub fn shatter(
+        obstacle: &Obstacle,
+        transform: &GlobalTransform,
+        impact_velocity: Vec3,
+        seed: u32,
+        level: &Level,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        textures: &TextureAssets,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<Entity> {
+        let (_, rotation, _) = transform.to_scale_rotation_translation();
+        let mut rng = StdRng::seed_from_u64(
+            ((seed as u64) << 32)
+                ^ ((obstacle.vertex.x as u32 as u64) << 16)
+                ^ (obstacle.vertex.z as u32 as u64),
+        );
+        let mut mesh_handles: Vec<(Vec3, Handle<Mesh>)> = vec![];
+        let mut material_handles = HashMap::new();
+        let mut entities = vec![];
+        for fragment in obstacle.kind.fragments(level.quad_size) {
+            let translation = transform.transform_point(fragment.translation);
+            let surface = TerrainChunk::surface_position(
+                translation,
+                level.chunk_size,
+                level.quad_size,
+                terrain_noise,
+            );
+            if translation.y < surface.y {
+                continue;
+            }
+            let mesh = match mesh_handles.iter().find(|(size, _)| *size == fragment.size) {
+                Some((_, handle)) => handle.clone(),
+                None => {
+                    let size = fragment.size;
+                    let handle = meshes.add(shape::Box::new(size.x, size.y, size.z).into());
+                    mesh_handles.push((size, handle.clone()));
+                    handle
+                }
+            };
+            let material = material_handles
+                .entry(fragment.surface)
+                .or_insert_with(|| materials.add(fragment.surface.material(textures)))
+                .clone();
+            let scatter = Vec3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            ) * Self::SCATTER_SPEED;
+            let spin = Vec3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            ) * Self::SCATTER_SPEED;
+            entities.push(
+                commands
+                    .spawn((
+                        Name::new("Debris"),
+                        Debris::new(),
+                        RigidBody::Dynamic,
+                        Collider::cuboid(fragment.size.x, fragment.size.y, fragment.size.z),
+                        GameLayer::debris(),
+                        ColliderDensity(Self::DENSITY),
+                        LinearVelocity(impact_velocity * Self::CARRIED_VELOCITY + scatter),
+                        AngularVelocity(spin),
+                        PbrBundle {
+                            mesh,
+                            material,
+                            transform: Transform::from_translation(translation)
+                                .with_rotation(rotation),
+                            ..Default::default()
+                        },
+                    ))
+                    .id(),
+            );
+        }
+        entities
+    }
+}
+
+impl Default for Debris {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,452 @@
+use std::f32::consts::FRAC_PI_2;
+
+use serde::{Deserialize, Serialize};
+
+use bevy_xpbd_3d::components::{Collider, RigidBody};
+
+use bevy::prelude::*;
+
+use crate::TextureAssets;
+
+// Every kind of obstacle that can be generated on the hill or placed in a course
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Reflect, Serialize, Deserialize)]
+pub enum ObstacleKind {
+    // width in quads, extending in +X from the anchor vertex
+    Wall { width: i32 },
+    // width in quads, with a gap in the middle that the cheese can squeeze through
+    Fence { width: i32 },
+    Post,
+    Pillar,
+    HayBale,
+    Boulder,
+    Stump,
+    // rolls down the hill once it is spawned
+    RollingLog,
+    // swings around a hinge post at its anchor
+    SwingingGate,
+    // slides back and forth across the hill
+    SlidingBarrier,
+    // waits for the cheese to approach before rolling down the hill
+    BarrelDrop,
+    // drives back and forth across the slope
+    Tractor,
+}
+
+// One piece of an obstacle's shape, relative to the obstacle's transform
+pub struct ObstaclePart {
+    pub transform: Transform,
+    pub collider: Collider,
+    pub mesh: Mesh,
+    pub surface: ObstacleSurface,
+}
+
+impl ObstaclePart {
+    fn new(transform: Transform, collider: Collider, mesh: Mesh, surface: ObstacleSurface) -> Self {
+        Self {
+            transform,
+            collider,
+            mesh,
+            surface,
+        }
+    }
+
+    fn block(translation: Vec3, size: Vec3, surface: ObstacleSurface) -> Self {
+        Self::new(
+            Transform::from_translation(translation),
+            Collider::cuboid(size.x, size.y, size.z),
+            shape::Box::new(size.x, size.y, size.z).into(),
+            surface,
+        )
+    }
+
+    fn column(height: f32, radius: f32, surface: ObstacleSurface) -> Self {
+        Self::new(
+            Transform::default(),
+            Collider::cylinder(height, radius),
+            Mesh::from(shape::Cylinder {
+                radius,
+                height,
+                ..Default::default()
+            }),
+            surface,
+        )
+    }
+
+    // a cylinder lying across the hill
+    fn roll(length: f32, radius: f32, surface: ObstacleSurface) -> Self {
+        let mut part = Self::column(length, radius, surface);
+        part.transform.rotate_z(FRAC_PI_2);
+        part
+    }
+}
+
+// A pre-fractured piece of a destructible obstacle, relative to the obstacle's transform
+#[derive(Clone, Copy, Debug)]
+pub struct ObstacleFragment {
+    pub translation: Vec3,
+    pub size: Vec3,
+    pub surface: ObstacleSurface,
+}
+
+impl ObstacleFragment {
+    // splits a box into cells no larger than `cell`, skipping any that `keep` rejects
+    fn grid(
+        size: Vec3,
+        cell: Vec3,
+        surface: ObstacleSurface,
+        keep: impl Fn(Vec3) -> bool,
+    ) -> Vec<Self> {
+        let counts = (size / cell).ceil().max(Vec3::ONE);
+        let cell = size / counts;
+        let mut fragments = vec![];
+        for x in 0..counts.x as usize {
+            for y in 0..counts.y as usize {
+                for z in 0..counts.z as usize {
+                    let translation =
+                        (Vec3::new(x as f32, y as f32, z as f32) + 0.5) * cell - size / 2.;
+                    if keep(translation) {
+                        fragments.push(Self {
+                            translation,
+                            size: cell,
+                            surface,
+                        });
+                    }
+                }
+            }
+        }
+        fragments
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObstacleSurface {
+    Bricks,
+    Wood,
+    Bark,
+    Hay,
+    Stone,
+    Paint,
+    Rubber,
+}
+
+impl ObstacleSurface {
+    pub fn material(&self, textures: &TextureAssets) -> StandardMaterial {
+        match self {
+            ObstacleSurface::Bricks => StandardMaterial {
+                base_color_texture: Some(textures.bricks.clone()),
+                ..Default::default()
+            },
+            ObstacleSurface::Wood => Color::rgb(0.45, 0.3, 0.15).into(),
+            ObstacleSurface::Bark => Color::rgb(0.3, 0.2, 0.12).into(),
+            ObstacleSurface::Hay => Color::rgb(0.85, 0.7, 0.3).into(),
+            ObstacleSurface::Paint => Color::rgb(0.75, 0.15, 0.1).into(),
+            ObstacleSurface::Rubber => StandardMaterial {
+                base_color: Color::rgb(0.08, 0.08, 0.08),
+                perceptual_roughness: 1.,
+                ..Default::default()
+            },
+            ObstacleSurface::Stone => StandardMaterial {
+                base_color: Color::rgb(0.5, 0.5, 0.48),
+                perceptual_roughness: 0.9,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ObstacleKind {
+    // tall obstacles are sunk into the hill so that they stand whatever the terrain noise does
+    const BURIED_HEIGHT: f32 = 40.;
+    const POST_RADIUS: f32 = 0.4;
+    const PILLAR_WIDTH: f32 = 3.;
+    const FENCE_HEIGHT: f32 = 2.4;
+    const FENCE_GAP: f32 = 6.;
+    const FENCE_POST_SPACING: f32 = 4.;
+    const HAY_BALE_RADIUS: f32 = 1.1;
+    const HAY_BALE_LENGTH: f32 = 2.4;
+    const BOULDER_RADIUS: f32 = 1.8;
+    const STUMP_RADIUS: f32 = 0.8;
+    const STUMP_HEIGHT: f32 = 1.4;
+    const LOG_RADIUS: f32 = 0.6;
+    const LOG_LENGTH: f32 = 8.;
+    const GATE_LENGTH: f32 = 10.;
+    const BARRIER_SIZE: Vec3 = Vec3::new(8., 1.6, 1.);
+    const BARREL_RADIUS: f32 = 0.7;
+    const BARREL_LENGTH: f32 = 1.8;
+    const TRACTOR_SIZE: Vec3 = Vec3::new(4.4, 3.4, 2.6);
+
+    pub fn label(&self) -> String {
+        match self {
+            ObstacleKind::Wall { width } => format!("Wall ({})", width),
+            ObstacleKind::Fence { width } => format!("Fence ({})", width),
+            ObstacleKind::Post => "Post".to_string(),
+            ObstacleKind::Pillar => "Pillar".to_string(),
+            ObstacleKind::HayBale => "Hay Bale".to_string(),
+            ObstacleKind::Boulder => "Boulder".to_string(),
+            ObstacleKind::Stump => "Stump".to_string(),
+            ObstacleKind::RollingLog => "Rolling Log".to_string(),
+            ObstacleKind::SwingingGate => "Swinging Gate".to_string(),
+            ObstacleKind::SlidingBarrier => "Sliding Barrier".to_string(),
+            ObstacleKind::BarrelDrop => "Barrel Drop".to_string(),
+            ObstacleKind::Tractor => "Tractor".to_string(),
+        }
+    }
+
+    // how many vertices the obstacle spans along X from its anchor
+    pub fn extent(&self) -> i32 {
+        match self {
+            ObstacleKind::Wall { width } | ObstacleKind::Fence { width } => *width,
+            _ => 0,
+        }
+    }
+
+    // the size of the obstacle's bounding box before it is placed on the hill
+    pub fn footprint(&self, quad_size: Vec2) -> Vec3 {
+        match self {
+            ObstacleKind::Wall { width } => Vec3::new(
+                *width as f32 * quad_size.x,
+                Self::BURIED_HEIGHT,
+                quad_size.y,
+            ),
+            ObstacleKind::Fence { width } => {
+                Vec3::new(*width as f32 * quad_size.x, Self::FENCE_HEIGHT, 0.3)
+            }
+            ObstacleKind::Post => Vec3::new(
+                Self::POST_RADIUS * 2.,
+                Self::BURIED_HEIGHT,
+                Self::POST_RADIUS * 2.,
+            ),
+            ObstacleKind::Pillar => {
+                Vec3::new(Self::PILLAR_WIDTH, Self::BURIED_HEIGHT, Self::PILLAR_WIDTH)
+            }
+            ObstacleKind::HayBale => Vec3::new(
+                Self::HAY_BALE_LENGTH,
+                Self::HAY_BALE_RADIUS * 2.,
+                Self::HAY_BALE_RADIUS * 2.,
+            ),
+            ObstacleKind::Boulder => Vec3::splat(Self::BOULDER_RADIUS * 2.),
+            ObstacleKind::Stump => Vec3::new(
+                Self::STUMP_RADIUS * 2.,
+                Self::STUMP_HEIGHT,
+                Self::STUMP_RADIUS * 2.,
+            ),
+            ObstacleKind::RollingLog => Vec3::new(
+                Self::LOG_LENGTH,
+                Self::LOG_RADIUS * 2.,
+                Self::LOG_RADIUS * 2.,
+            ),
+            // the gate pivots around its hinge, so it is centered on the post
+            ObstacleKind::SwingingGate => {
+                Vec3::new(Self::GATE_LENGTH * 2., Self::FENCE_HEIGHT, 0.3)
+            }
+            ObstacleKind::SlidingBarrier => Self::BARRIER_SIZE,
+            ObstacleKind::BarrelDrop => Vec3::new(
+                Self::BARREL_LENGTH,
+                Self::BARREL_RADIUS * 2.,
+                Self::BARREL_RADIUS * 2.,
+            ),
+            ObstacleKind::Tractor => Self::TRACTOR_SIZE,
+        }
+    }
+
+    // buried obstacles ignore the terrain, everything else rests on its surface
+    pub fn is_buried(&self) -> bool {
+        matches!(
+            self,
+            ObstacleKind::Wall { .. } | ObstacleKind::Post | ObstacleKind::Pillar
+        )
+    }
+
+    pub fn rigid_body(&self) -> RigidBody {
+        match self {
+            ObstacleKind::RollingLog => RigidBody::Dynamic,
+            ObstacleKind::SwingingGate
+            | ObstacleKind::SlidingBarrier
+            | ObstacleKind::BarrelDrop
+            | ObstacleKind::Tractor => RigidBody::Kinematic,
+            _ => RigidBody::Static,
+        }
+    }
+
+    pub fn parts(&self, quad_size: Vec2) -> Vec<ObstaclePart> {
+        let footprint = self.footprint(quad_size);
+        match self {
+            ObstacleKind::Wall { .. } => vec![ObstaclePart::block(
+                Vec3::ZERO,
+                footprint,
+                ObstacleSurface::Bricks,
+            )],
+            ObstacleKind::Fence { .. } => Self::fence_parts(footprint),
+            ObstacleKind::Post => vec![ObstaclePart::column(
+                Self::BURIED_HEIGHT,
+                Self::POST_RADIUS,
+                ObstacleSurface::Wood,
+            )],
+            ObstacleKind::Pillar => vec![ObstaclePart::block(
+                Vec3::ZERO,
+                footprint,
+                ObstacleSurface::Bricks,
+            )],
+            ObstacleKind::HayBale => vec![ObstaclePart::roll(
+                Self::HAY_BALE_LENGTH,
+                Self::HAY_BALE_RADIUS,
+                ObstacleSurface::Hay,
+            )],
+            ObstacleKind::Boulder => vec![ObstaclePart::new(
+                Transform::default(),
+                Collider::ball(Self::BOULDER_RADIUS),
+                Mesh::from(shape::UVSphere {
+                    radius: Self::BOULDER_RADIUS,
+                    sectors: 12,
+                    stacks: 8,
+                }),
+                ObstacleSurface::Stone,
+            )],
+            ObstacleKind::Stump => vec![ObstaclePart::column(
+                Self::STUMP_HEIGHT,
+                Self::STUMP_RADIUS,
+                ObstacleSurface::Bark,
+            )],
+            ObstacleKind::RollingLog => vec![ObstaclePart::roll(
+                Self::LOG_LENGTH,
+                Self::LOG_RADIUS,
+                ObstacleSurface::Bark,
+            )],
+            ObstacleKind::SwingingGate => vec![
+                ObstaclePart::column(footprint.y, 0.2, ObstacleSurface::Wood),
+                ObstaclePart::block(
+                    Vec3::new(Self::GATE_LENGTH / 2., 0.1, 0.),
+                    Vec3::new(Self::GATE_LENGTH, footprint.y * 0.7, footprint.z),
+                    ObstacleSurface::Wood,
+                ),
+            ],
+            ObstacleKind::SlidingBarrier => vec![ObstaclePart::block(
+                Vec3::ZERO,
+                footprint,
+                ObstacleSurface::Paint,
+            )],
+            ObstacleKind::BarrelDrop => vec![ObstaclePart::roll(
+                Self::BARREL_LENGTH,
+                Self::BARREL_RADIUS,
+                ObstacleSurface::Wood,
+            )],
+            ObstacleKind::Tractor => Self::tractor_parts(footprint),
+        }
+    }
+
+    // the impulse from the cheese (in N·s) that shatters the obstacle, if it can be broken
+    pub fn break_impulse(&self) -> Option<f32> {
+        match self {
+            ObstacleKind::Wall { .. } => Some(14000.),
+            ObstacleKind::Pillar => Some(12000.),
+            ObstacleKind::Stump => Some(8000.),
+            ObstacleKind::Post => Some(5000.),
+            ObstacleKind::Fence { .. } => Some(3000.),
+            ObstacleKind::HayBale => Some(2500.),
+            _ => None,
+        }
+    }
+
+    // the pieces that the obstacle breaks into, see `break_impulse`
+    pub fn fragments(&self, quad_size: Vec2) -> Vec<ObstacleFragment> {
+        let footprint = self.footprint(quad_size);
+        let keep_all = |_| true;
+        match self {
+            ObstacleKind::Wall { .. } => ObstacleFragment::grid(
+                footprint,
+                Vec3::new(5., 5., footprint.z),
+                ObstacleSurface::Bricks,
+                keep_all,
+            ),
+            ObstacleKind::Pillar => ObstacleFragment::grid(
+                footprint,
+                Vec3::new(1.5, 3., 1.5),
+                ObstacleSurface::Bricks,
+                keep_all,
+            ),
+            ObstacleKind::Stump => {
+                ObstacleFragment::grid(footprint, footprint / 2., ObstacleSurface::Bark, keep_all)
+            }
+            ObstacleKind::Post => ObstacleFragment::grid(
+                footprint,
+                Vec3::new(footprint.x, 2., footprint.z),
+                ObstacleSurface::Wood,
+                keep_all,
+            ),
+            ObstacleKind::Fence { .. } => {
+                let gap = Self::FENCE_GAP.min(footprint.x / 2.);
+                ObstacleFragment::grid(
+                    footprint,
+                    Vec3::new(2., footprint.y / 3., footprint.z),
+                    ObstacleSurface::Wood,
+                    move |translation| translation.x.abs() > gap / 2.,
+                )
+            }
+            ObstacleKind::HayBale => {
+                ObstacleFragment::grid(footprint, footprint / 2., ObstacleSurface::Hay, keep_all)
+            }
+            _ => vec![],
+        }
+    }
+
+    // a body and cab on four wheels, driving along x
+    fn tractor_parts(footprint: Vec3) -> Vec<ObstaclePart> {
+        let wheel_radius = footprint.y * 0.25;
+        let body_height = footprint.y * 0.35;
+        let body_y = -footprint.y / 2. + wheel_radius + body_height / 2.;
+        let mut parts = vec![
+            ObstaclePart::block(
+                Vec3::new(0., body_y, 0.),
+                Vec3::new(footprint.x, body_height, footprint.z * 0.7),
+                ObstacleSurface::Paint,
+            ),
+            ObstaclePart::block(
+                Vec3::new(-footprint.x * 0.2, body_y + body_height, 0.),
+                Vec3::new(footprint.x * 0.4, body_height * 1.4, footprint.z * 0.6),
+                ObstacleSurface::Paint,
+            ),
+        ];
+        for (x, z) in [(-1., -1.), (-1., 1.), (1., -1.), (1., 1.)] {
+            let mut wheel =
+                ObstaclePart::column(footprint.z * 0.15, wheel_radius, ObstacleSurface::Rubber);
+            wheel.transform = Transform::from_xyz(
+                x * (footprint.x / 2. - wheel_radius),
+                -footprint.y / 2. + wheel_radius,
+                z * (footprint.z / 2. - footprint.z * 0.075),
+            )
+            .with_rotation(Quat::from_rotation_x(FRAC_PI_2));
+            parts.push(wheel);
+        }
+        parts
+    }
+
+    // two runs of posts and rails either side of a gap in the middle
+    fn fence_parts(footprint: Vec3) -> Vec<ObstaclePart> {
+        let gap = Self::FENCE_GAP.min(footprint.x / 2.);
+        let run_length = (footprint.x - gap) / 2.;
+        let post_count = (run_length / Self::FENCE_POST_SPACING).ceil().max(1.) as usize + 1;
+        let post_spacing = run_length / (post_count - 1) as f32;
+        let mut parts = vec![];
+        for side in [-1., 1.] {
+            // the center of this run along x
+            let run_center = side * (gap + run_length) / 2.;
+            for post in 0..post_count {
+                let x = run_center - run_length / 2. + post as f32 * post_spacing;
+                parts.push(ObstaclePart::block(
+                    Vec3::new(x, 0., 0.),
+                    Vec3::new(0.3, footprint.y, footprint.z),
+                    ObstacleSurface::Wood,
+                ));
+            }
+            for rail_height in [0.1, 0.35] {
+                parts.push(ObstaclePart::block(
+                    Vec3::new(run_center, rail_height * footprint.y, 0.),
+                    Vec3::new(run_length, 0.2, footprint.z * 0.5),
+                    ObstacleSurface::Wood,
+                ));
+            }
+        }
+        parts
+    }
+}
@@ -1,22 +1,36 @@
 use ::noise::NoiseFn;
-use rand::Rng;
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use bevy::{prelude::*, utils::HashMap};
 
-use crate::{Level, TextureAssets, Vertex};
+use crate::{Course, DifficultyCurve, Level, TextureAssets, Vertex};
 
 use super::Chunk;
 
+mod debris;
+pub use debris::*;
+
+mod kind;
+pub use kind::*;
+
+mod motion;
+pub use motion::*;
+
 mod noise;
 pub use noise::*;
 
+mod obstacle;
+pub use obstacle::*;
+
+mod placement;
+pub use placement::*;
+
 mod plugin;
 pub use plugin::*;
 
-mod wall;
-pub use wall::*;
-
-pub struct Obstacle;
+mod registry;
+pub use registry::*;
 
 #[derive(Debug, Clone, Default)]
 #[derive(Component)]
@@ -31,24 +45,70 @@ impl Obstacles {
         }
     }
 
-    pub fn generate_obstacles_for_chunk<'a>(
-        &'a self,
+    // obstacles are generated in chunks whose origin mirrors the level chunk's z
+    pub fn chunk_for_level_origin(origin: Vertex, chunk_size: Vertex, quad_size: Vec2) -> Chunk {
+        Chunk {
+            quad_size,
+            size: chunk_size,
+            // IDK why this works but it does
+            origin: Vertex::new(origin.x, -origin.z),
+        }
+    }
+
+    // picks where obstacles go in the chunk and which kinds they are, then moves or drops any
+    // that would leave a row of the hill without a lane through it.
+    // the same seed always produces the same obstacles.
+    pub fn generate_obstacles_for_chunk(
+        chunk: Chunk,
+        seed: u32,
+        noise: &impl NoiseFn<f64, 2>,
+        registry: &ObstacleRegistry,
+        difficulty: &DifficultyCurve,
+    ) -> impl Iterator<Item = Obstacle> {
+        // obstacles near the edges of the chunks around this one can reach into it
+        let neighbours = (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter(|offset| *offset != (0, 0))
+            .flat_map(|(x, z)| {
+                let neighbour = Chunk {
+                    origin: Vertex::new(chunk.origin.x + x, chunk.origin.z + z),
+                    ..chunk.clone()
+                };
+                Self::candidate_obstacles_for_chunk(neighbour, seed, noise, registry, difficulty)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut planner = LanePlanner::new(&chunk, neighbours);
+        Self::candidate_obstacles_for_chunk(chunk, seed, noise, registry, difficulty)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(move |candidate| planner.place(candidate))
+    }
+
+    // seeds the rng from the race seed and the chunk so that the same race always produces the
+    // same candidates. the difficulty is sampled at the top of the chunk.
+    fn candidate_obstacles_for_chunk<'a>(
         chunk: Chunk,
+        seed: u32,
         noise: &'a impl NoiseFn<f64, 2>,
-        textures: &'a TextureAssets,
-        meshes: &'a mut Assets<Mesh>,
-        materials: &'a mut Assets<StandardMaterial>,
-    ) -> impl Iterator<Item = impl Bundle> + 'a {
-        let mut rng = rand::thread_rng();
+        registry: &'a ObstacleRegistry,
+        difficulty: &DifficultyCurve,
+    ) -> impl Iterator<Item = Obstacle> + 'a {
+        let mut rng = StdRng::seed_from_u64(
+            ((seed as u64) << 32)
+                ^ ((chunk.origin.x as u32 as u64) << 16)
+                ^ (chunk.origin.z as u32 as u64),
+        );
         let before_first_chunk = chunk.origin.z <= 0;
-        let before_fifth_chunk = chunk.origin.z < 5;
+        let settings =
+            difficulty.sample((chunk.origin.z * chunk.size.z) as f32 * chunk.quad_size.y);
         chunk
             .iter_by_row()
             // don't iterate along the final edge
             .take(((chunk.size.z - 1) * chunk.size.x) as usize)
             // skip by some arbitrary amount
             .step_by(rng.gen_range((chunk.size.x * 4)..(chunk.size.x * 4 + 7)) as usize)
-            // and make a wall there if the noise value is high enough
+            // and place an obstacle there if the noise value is high enough
             .filter_map(move |vertex| {
                 if before_first_chunk {
                     return None;
@@ -56,36 +116,30 @@ impl Obstacles {
                 let global_vertex = chunk.to_global_coords(vertex);
                 let position = chunk.to_translation(global_vertex);
                 let noise = noise.get([position.x as f64, position.y as f64]);
-                let noise_threshold = if before_fifth_chunk { 0.995 } else { 0.98 };
-                if noise > noise_threshold {
-                    info!("{:?} {} {}", global_vertex, position, noise);
-                    Some(
-                        Wall::new(
-                            chunk.clone(),
-                            vertex,
-                            Vec2::new(
-                                chunk.quad_size.x * chunk.size.x as f32 / 2.,
-                                chunk.quad_size.y,
-                            ),
-                        )
-                        .to_bundle(textures, meshes, materials),
-                    )
-                } else {
-                    None
+                if noise <= settings.obstacle_threshold {
+                    return None;
                 }
+                let kind =
+                    registry.choose(&mut rng, chunk.origin.z, settings.moving_obstacle_weight)?;
+                // shift wide obstacles back so that they end inside the chunk that spawned them
+                let anchor = Vertex::new(vertex.x.min(chunk.size.x - kind.extent()), vertex.z);
+                Some(Obstacle::new(kind, chunk.to_global_coords(anchor)))
             })
     }
 
-    pub fn update(
-        &mut self,
-        level: &Level,
-        noise: &impl NoiseFn<f64, 2>,
-        commands: &mut Commands,
-        textures: &TextureAssets,
-        meshes: &mut Assets<Mesh>,
-        materials: &mut Assets<StandardMaterial>,
-    ) {
-        // remove out-of-bounds chunks
+    // swap an entity for others in whichever chunk it belongs to, so that they unload together
+    pub fn replace_entity(&mut self, entity: Entity, replacements: Vec<Entity>) {
+        if let Some(entities) = self
+            .chunk_entities
+            .values_mut()
+            .find(|entities| entities.contains(&entity))
+        {
+            entities.retain(|other| *other != entity);
+            entities.extend(replacements);
+        }
+    }
+
+    fn despawn_chunks_out_of_play(&mut self, level: &Level, commands: &mut Commands) {
         let chunks_to_remove = self
             .chunk_entities
             .iter()
@@ -101,27 +155,76 @@ impl Obstacles {
         for vertex in chunks_to_remove {
             if let Some(entities) = self.chunk_entities.remove(&vertex) {
                 for entity in entities {
-                    commands.entity(entity).despawn();
+                    // debris may have already been cleaned up
+                    if let Some(entity_commands) = commands.get_entity(entity) {
+                        entity_commands.despawn_recursive();
+                    }
                 }
             }
         }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        level: &Level,
+        seed: u32,
+        noise: &impl NoiseFn<f64, 2>,
+        registry: &ObstacleRegistry,
+        difficulty: &DifficultyCurve,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        textures: &TextureAssets,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        self.despawn_chunks_out_of_play(level, commands);
 
         // spawn missing in-bounds chunks
         for origin in level.chunks_in_play.iter() {
             if !self.chunk_entities.contains_key(origin) {
-                let chunk = Chunk {
-                    quad_size: level.quad_size,
-                    size: level.chunk_size,
-                    // IDK why this works but it does
-                    origin: Vertex::new(origin.x, -origin.z),
-                };
-                let mut chunk_entities = vec![];
-                for bundle in
-                    self.generate_obstacles_for_chunk(chunk, noise, textures, meshes, materials)
-                {
-                    let entity = commands.spawn(bundle).id();
-                    chunk_entities.push(entity)
-                }
+                let chunk =
+                    Self::chunk_for_level_origin(*origin, level.chunk_size, level.quad_size);
+                let chunk_entities =
+                    Self::generate_obstacles_for_chunk(chunk, seed, noise, registry, difficulty)
+                        .map(|obstacle| {
+                            obstacle.spawn(
+                                level,
+                                terrain_noise,
+                                commands,
+                                textures,
+                                meshes,
+                                materials,
+                            )
+                        })
+                        .collect();
+                self.chunk_entities.insert(*origin, chunk_entities);
+            }
+        }
+    }
+
+    // stream the pieces of a hand-made course instead of generating obstacles from noise
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_from_course(
+        &mut self,
+        level: &Level,
+        course: &Course,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        textures: &TextureAssets,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        self.despawn_chunks_out_of_play(level, commands);
+
+        for origin in level.chunks_in_play.iter() {
+            if !self.chunk_entities.contains_key(origin) {
+                let chunk_entities = course
+                    .pieces_in_chunk(*origin, level)
+                    .map(|piece| {
+                        piece.spawn(level, terrain_noise, commands, textures, meshes, materials)
+                    })
+                    .collect();
                 self.chunk_entities.insert(*origin, chunk_entities);
             }
         }
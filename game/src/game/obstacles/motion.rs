@@ -0,0 +1,126 @@
+use std::f32::consts::TAU;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use bevy::prelude::*;
+
+use crate::Vertex;
+
+use super::ObstacleKind;
+
+// Scripted movement for kinematic obstacles. Poses are a function of the race seed and
+// `RaceClock` alone, so the same race always plays out the same way.
+#[derive(Clone, Debug)]
+#[derive(Component, Reflect)]
+pub struct ObstacleMotion {
+    pub pattern: MotionPattern,
+    // where the obstacle was placed on the hill, which the motion is relative to
+    pub origin: Transform,
+    // how far through its cycle the obstacle starts, in radians
+    pub phase: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub enum MotionPattern {
+    // rotate back and forth around the local Y axis
+    Swing { angle: f32, period: f32 },
+    // move back and forth along the local X axis
+    Slide { distance: f32, period: f32 },
+    // hold still until the cheese is within `distance` up the hill, then roll down it
+    Release { distance: f32, speed: f32 },
+}
+
+impl MotionPattern {
+    pub fn for_kind(kind: ObstacleKind) -> Option<Self> {
+        match kind {
+            ObstacleKind::SwingingGate => Some(MotionPattern::Swing {
+                angle: 70_f32.to_radians(),
+                period: 4.,
+            }),
+            ObstacleKind::SlidingBarrier => Some(MotionPattern::Slide {
+                distance: 6.,
+                period: 3.,
+            }),
+            ObstacleKind::BarrelDrop => Some(MotionPattern::Release {
+                distance: 60.,
+                speed: 8.,
+            }),
+            ObstacleKind::Tractor => Some(MotionPattern::Slide {
+                distance: 24.,
+                period: 20.,
+            }),
+            _ => None,
+        }
+    }
+
+    // how far either side of its origin the obstacle travels across the hill
+    pub fn sweep(&self) -> f32 {
+        match self {
+            MotionPattern::Slide { distance, .. } => *distance,
+            MotionPattern::Swing { .. } | MotionPattern::Release { .. } => 0.,
+        }
+    }
+
+    // how far down the hill the obstacle travels from its origin. a released obstacle is let go
+    // `distance` ahead of the cheese, which is taken to overtake it within that distance again
+    pub fn reach(&self) -> f32 {
+        match self {
+            MotionPattern::Release { distance, .. } => *distance,
+            MotionPattern::Swing { .. } | MotionPattern::Slide { .. } => 0.,
+        }
+    }
+}
+
+// A pose along with the velocities that reach it, so that collisions carry momentum
+pub struct MotionPose {
+    pub transform: Transform,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+}
+
+impl ObstacleMotion {
+    pub fn new(kind: ObstacleKind, vertex: Vertex, origin: Transform, seed: u32) -> Option<Self> {
+        let pattern = MotionPattern::for_kind(kind)?;
+        // mix the obstacle's position into the seed so that neighbours move out of step
+        let mut rng = StdRng::seed_from_u64(
+            ((seed as u64) << 32) ^ ((vertex.x as u32 as u64) << 16) ^ (vertex.z as u32 as u64),
+        );
+        Some(Self {
+            pattern,
+            origin,
+            phase: rng.gen_range(0.0..TAU),
+        })
+    }
+
+    // the pose `time` seconds into the race, or None if the motion isn't scripted
+    pub fn pose(&self, time: f32) -> Option<MotionPose> {
+        match self.pattern {
+            MotionPattern::Swing { angle, period } => {
+                let frequency = TAU / period;
+                let t = frequency * time + self.phase;
+                let axis = self.origin.rotation * Vec3::Y;
+                Some(MotionPose {
+                    transform: self.origin.with_rotation(
+                        self.origin.rotation * Quat::from_rotation_y(angle * t.sin()),
+                    ),
+                    linear_velocity: Vec3::ZERO,
+                    angular_velocity: axis * angle * frequency * t.cos(),
+                })
+            }
+            MotionPattern::Slide { distance, period } => {
+                let frequency = TAU / period;
+                let t = frequency * time + self.phase;
+                let axis = self.origin.rotation * Vec3::X;
+                Some(MotionPose {
+                    transform: self
+                        .origin
+                        .with_translation(self.origin.translation + axis * distance * t.sin()),
+                    linear_velocity: axis * distance * frequency * t.cos(),
+                    angular_velocity: Vec3::ZERO,
+                })
+            }
+            MotionPattern::Release { .. } => None,
+        }
+    }
+}
@@ -19,7 +19,7 @@ impl ObstacleNoise {
     }
 
     fn generate_noise(seed: u32) -> impl NoiseFn<f64, 2> {
-        RidgedMulti::<Perlin>::new(seed + 17)
+        RidgedMulti::<Perlin>::new(seed.wrapping_add(17))
             .set_frequency(4.0)
             .set_lacunarity(2.162109375)
             .set_octaves(2)
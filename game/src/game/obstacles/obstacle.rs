@@ -0,0 +1,108 @@
+use ::noise::NoiseFn;
+use bevy_xpbd_3d::components::ColliderDensity;
+
+use bevy::prelude::*;
+
+use crate::{GameLayer, Level, TerrainChunk, TextureAssets, Vertex};
+
+use super::ObstacleKind;
+
+#[derive(Clone, Debug)]
+#[derive(Component, Reflect)]
+pub struct Obstacle {
+    pub kind: ObstacleKind,
+    // in global grid units, see `Chunk::to_global_coords`
+    pub vertex: Vertex,
+}
+
+impl Obstacle {
+    // how far resting obstacles sink into the ground so that they don't float on bumps
+    const SINK_DEPTH: f32 = 0.15;
+    // how far above the ground dynamic obstacles are dropped
+    const DROP_HEIGHT: f32 = 0.3;
+
+    pub fn new(kind: ObstacleKind, vertex: Vertex) -> Self {
+        Self { kind, vertex }
+    }
+
+    // the obstacle's center on the flat grid, before it is placed on the hill
+    pub(super) fn grid_translation(&self, quad_size: Vec2) -> Vec3 {
+        Vec3::new(
+            (self.vertex.x as f32 + self.kind.extent() as f32 / 2.) * quad_size.x,
+            0.,
+            self.vertex.z as f32 * quad_size.y,
+        )
+    }
+
+    pub fn transform(
+        &self,
+        chunk_size: Vertex,
+        quad_size: Vec2,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+    ) -> Transform {
+        let footprint = self.kind.footprint(quad_size);
+        let grid_translation = self.grid_translation(quad_size);
+        if self.kind.is_buried() {
+            let sloped_translation = Vec3::new(
+                grid_translation.x,
+                -grid_translation.z + footprint.y * 0.45,
+                grid_translation.z,
+            );
+            return Transform::from_translation(sloped_translation)
+                .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_8));
+        }
+
+        let surface =
+            TerrainChunk::surface_position(grid_translation, chunk_size, quad_size, terrain_noise);
+        let normal =
+            TerrainChunk::surface_normal(grid_translation, chunk_size, quad_size, terrain_noise);
+        let lift = if self.kind.rigid_body().is_dynamic() {
+            footprint.y / 2. + Self::DROP_HEIGHT
+        } else {
+            footprint.y / 2. - Self::SINK_DEPTH
+        };
+        Transform::from_translation(surface + normal * lift)
+            .with_rotation(Quat::from_rotation_arc(Vec3::Y, normal))
+    }
+
+    pub fn spawn(
+        self,
+        level: &Level,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        textures: &TextureAssets,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        let transform = self.transform(level.chunk_size, level.quad_size, terrain_noise);
+        let parts = self.kind.parts(level.quad_size);
+        commands
+            .spawn((
+                Name::new(format!(
+                    "{} ({},{})",
+                    self.kind.label(),
+                    self.vertex.x,
+                    self.vertex.z
+                )),
+                self.kind.rigid_body(),
+                SpatialBundle::from_transform(transform),
+                self,
+            ))
+            .with_children(|builder| {
+                for part in parts {
+                    builder.spawn((
+                        part.collider,
+                        GameLayer::obstacles(),
+                        ColliderDensity(1.),
+                        PbrBundle {
+                            mesh: meshes.add(part.mesh),
+                            material: materials.add(part.surface.material(textures)),
+                            transform: part.transform,
+                            ..Default::default()
+                        },
+                    ));
+                }
+            })
+            .id()
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use crate::{Chunk, Vertex};
+
+use super::{MotionPattern, Obstacle};
+
+// The space an obstacle blocks across the hill: an X interval in world units over a range of
+// global vertex rows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObstacleSpan {
+    pub min_x: f32,
+    pub max_x: f32,
+    pub min_row: i32,
+    pub max_row: i32,
+}
+
+impl ObstacleSpan {
+    fn covers_row(&self, row: i32) -> bool {
+        self.min_row <= row && row <= self.max_row
+    }
+
+    fn overlaps(&self, other: &ObstacleSpan) -> bool {
+        self.min_x < other.max_x
+            && other.min_x < self.max_x
+            && self.min_row <= other.max_row
+            && other.min_row <= self.max_row
+    }
+}
+
+impl Obstacle {
+    // includes the full reach of obstacles that slide back and forth or roll down the hill
+    pub fn span(&self, quad_size: Vec2) -> ObstacleSpan {
+        let center = self.grid_translation(quad_size);
+        let footprint = self.kind.footprint(quad_size);
+        let pattern = MotionPattern::for_kind(self.kind);
+        let half_width = footprint.x / 2. + pattern.map_or(0., |pattern| pattern.sweep());
+        // the hill runs down towards +Z
+        let reach = pattern.map_or(0., |pattern| pattern.reach());
+        ObstacleSpan {
+            min_x: center.x - half_width,
+            max_x: center.x + half_width,
+            min_row: ((center.z - footprint.z / 2.) / quad_size.y).ceil() as i32,
+            max_row: ((center.z + footprint.z / 2. + reach) / quad_size.y).floor() as i32,
+        }
+    }
+}
+
+// Makes sure that every row of a chunk keeps a lane wide enough for the cheese to roll through.
+//
+// Each chunk only moves or drops its own obstacles, and treats the unvalidated obstacles of the
+// chunks around it as fixed. Validation only ever removes those obstacles or moves them to spots
+// that lie entirely inside their own chunk, so every chunk's lanes hold however its neighbours
+// turn out, and the result depends on nothing but the chunk's own candidates.
+pub struct LanePlanner {
+    min_x: f32,
+    max_x: f32,
+    min_row: i32,
+    max_row: i32,
+    // in vertices
+    width: i32,
+    quad_size: Vec2,
+    spans: Vec<ObstacleSpan>,
+}
+
+impl LanePlanner {
+    // several cheeses wide, so that there is room to steer into the gap
+    pub const MIN_LANE_WIDTH: f32 = 6.;
+
+    // `neighbours` are treated as already placed
+    pub fn new(chunk: &Chunk, neighbours: impl IntoIterator<Item = Obstacle>) -> Self {
+        let top = chunk.to_global_coords(Vertex::new(0, 0));
+        let bottom = chunk.to_global_coords(Vertex::new(chunk.size.x, chunk.size.z - 1));
+        Self {
+            min_x: top.x as f32 * chunk.quad_size.x,
+            max_x: bottom.x as f32 * chunk.quad_size.x,
+            min_row: bottom.z,
+            max_row: top.z,
+            width: chunk.size.x,
+            quad_size: chunk.quad_size,
+            spans: neighbours
+                .into_iter()
+                .map(|obstacle| obstacle.span(chunk.quad_size))
+                .collect(),
+        }
+    }
+
+    // keeps the candidate where it is if the lanes allow it, otherwise slides it along its row
+    // to the nearest spot that does, or drops it if there is none
+    pub fn place(&mut self, candidate: Obstacle) -> Option<Obstacle> {
+        let span = candidate.span(self.quad_size);
+        if self.keeps_lanes(&span) {
+            self.spans.push(span);
+            return Some(candidate);
+        }
+        // moving an obstacle that reaches into another chunk's rows could block that chunk's lane
+        if span.min_row >= self.min_row && span.max_row <= self.max_row {
+            let moved = (1..=self.width)
+                .flat_map(|offset| [offset, -offset])
+                .map(|offset| {
+                    Obstacle::new(
+                        candidate.kind,
+                        Vertex::new(candidate.vertex.x + offset, candidate.vertex.z),
+                    )
+                })
+                .map(|obstacle| (obstacle.span(self.quad_size), obstacle))
+                .find(|(span, _)| {
+                    span.min_x >= self.min_x
+                        && span.max_x <= self.max_x
+                        // don't stack the obstacle on top of another one
+                        && !self.spans.iter().any(|other| other.overlaps(span))
+                        && self.keeps_lanes(span)
+                });
+            if let Some((span, obstacle)) = moved {
+                debug!(
+                    "Moved {:?} from {:?} to {:?} to keep a lane open",
+                    obstacle.kind, candidate.vertex, obstacle.vertex
+                );
+                self.spans.push(span);
+                return Some(obstacle);
+            }
+        }
+        debug!(
+            "Dropped {:?} at {:?} to keep a lane open",
+            candidate.kind, candidate.vertex
+        );
+        None
+    }
+
+    // the narrowest of the widest lanes through each of the chunk's rows
+    pub fn narrowest_lane(&self) -> f32 {
+        (self.min_row..=self.max_row)
+            .map(|row| self.widest_lane(row, None))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    fn keeps_lanes(&self, span: &ObstacleSpan) -> bool {
+        (span.min_row.max(self.min_row)..=span.max_row.min(self.max_row))
+            .all(|row| self.widest_lane(row, Some(span)) >= Self::MIN_LANE_WIDTH)
+    }
+
+    // the widest gap in the chunk's row, if the span were added to it
+    fn widest_lane(&self, row: i32, span: Option<&ObstacleSpan>) -> f32 {
+        let mut blocked = self
+            .spans
+            .iter()
+            .chain(span)
+            .filter(|span| span.covers_row(row))
+            .map(|span| (span.min_x, span.max_x))
+            .collect::<Vec<_>>();
+        blocked.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut widest: f32 = 0.;
+        let mut lane_start = self.min_x;
+        for (min_x, max_x) in blocked {
+            widest = widest.max(min_x.min(self.max_x) - lane_start);
+            lane_start = lane_start.max(max_x);
+        }
+        widest.max(self.max_x - lane_start)
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use crate::{AppState, ObstacleNoise};
+use crate::{
+    AppState, Biome, ObstacleNoise, ObstacleRegistry, RaceSeedSet, TerrainNoise, TextureAssets,
+};
 
 mod systems;
 
@@ -9,11 +11,44 @@ pub struct ObstaclesPlugin;
 
 impl Plugin for ObstaclesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            systems::update_obstacles.run_if(resource_exists::<ObstacleNoise>()),
-        )
-        .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
-        .add_systems(Update, systems::attach_obstacles);
+        app.init_resource::<Biome>()
+            .init_resource::<ObstacleRegistry>()
+            .add_systems(
+                Update,
+                // the editor spawns course pieces itself so that they can be selected and moved
+                systems::update_obstacles.run_if(
+                    resource_exists::<ObstacleNoise>()
+                        // resting obstacles are placed on the terrain's surface
+                        .and_then(resource_exists::<TerrainNoise>())
+                        .and_then(not(in_state(AppState::Editor))),
+                ),
+            )
+            .add_systems(
+                OnEnter(AppState::SpawningScene),
+                systems::seed_noise.after(RaceSeedSet),
+            )
+            .add_systems(Update, systems::attach_obstacles)
+            .add_systems(
+                Update,
+                (
+                    systems::attach_motion,
+                    systems::animate_obstacles,
+                    systems::release_obstacles,
+                )
+                    .chain()
+                    // pieces stay where they are placed while the course is being edited
+                    .run_if(not(in_state(AppState::Editor))),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::break_obstacles.run_if(
+                        resource_exists::<TerrainNoise>()
+                            .and_then(resource_exists::<TextureAssets>()),
+                    ),
+                    systems::expire_debris,
+                )
+                    .run_if(in_state(AppState::Racing)),
+            );
     }
 }
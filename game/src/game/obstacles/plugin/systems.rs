@@ -1,9 +1,14 @@
 use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
 
-use crate::{Level, ObstacleNoise, Obstacles, TextureAssets};
+use crate::{
+    impact_speed, Cheese, Course, Debris, DifficultyCurve, Level, MotionPattern, Obstacle,
+    ObstacleMotion, ObstacleNoise, ObstacleRegistry, Obstacles, PreviousVelocity, RaceClock,
+    RaceSeed, TerrainNoise, TextureAssets,
+};
 
-pub(super) fn seed_noise(mut commands: Commands) {
-    commands.insert_resource(ObstacleNoise::new(rand::random()));
+pub(super) fn seed_noise(mut commands: Commands, seed: Res<RaceSeed>) {
+    commands.insert_resource(ObstacleNoise::new(seed.0));
 }
 
 pub(super) fn attach_obstacles(mut commands: Commands, query: Query<Entity, Added<Level>>) {
@@ -12,22 +17,202 @@ pub(super) fn attach_obstacles(mut commands: Commands, query: Query<Entity, Adde
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn update_obstacles(
     mut commands: Commands,
     mut obstacles_query: Query<(&mut Obstacles, &Level)>,
+    seed: Res<RaceSeed>,
     noise: Res<ObstacleNoise>,
+    registry: Res<ObstacleRegistry>,
+    difficulty: Res<DifficultyCurve>,
+    terrain_noise: Res<TerrainNoise>,
+    course: Option<Res<Course>>,
     textures: Res<TextureAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     for (mut obstacles, level) in obstacles_query.iter_mut() {
-        obstacles.update(
+        if let Some(course) = course.as_ref() {
+            obstacles.update_from_course(
+                level,
+                course,
+                &terrain_noise.get(),
+                &mut commands,
+                &textures,
+                &mut meshes,
+                &mut materials,
+            );
+        } else {
+            obstacles.update(
+                level,
+                seed.0,
+                &noise.get(),
+                &registry,
+                &difficulty,
+                &terrain_noise.get(),
+                &mut commands,
+                &textures,
+                &mut meshes,
+                &mut materials,
+            );
+        }
+    }
+}
+
+pub(super) fn attach_motion(
+    mut commands: Commands,
+    query: Query<(Entity, &Obstacle, &Transform), Added<Obstacle>>,
+    seed: Res<RaceSeed>,
+) {
+    for (entity, obstacle, transform) in query.iter() {
+        if let Some(motion) =
+            ObstacleMotion::new(obstacle.kind, obstacle.vertex, *transform, seed.0)
+        {
+            commands.entity(entity).insert((
+                motion,
+                LinearVelocity(Vec3::ZERO),
+                AngularVelocity(Vec3::ZERO),
+            ));
+        }
+    }
+}
+
+pub(super) fn animate_obstacles(
+    mut query: Query<(
+        &ObstacleMotion,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    clock: Res<RaceClock>,
+) {
+    for (motion, mut transform, mut linear_velocity, mut angular_velocity) in query.iter_mut() {
+        if let Some(pose) = motion.pose(clock.0) {
+            *transform = pose.transform;
+            linear_velocity.0 = pose.linear_velocity;
+            angular_velocity.0 = pose.angular_velocity;
+        }
+    }
+}
+
+pub(super) fn release_obstacles(
+    mut commands: Commands,
+    query: Query<(Entity, &ObstacleMotion)>,
+    cheese_query: Query<&Transform, With<Cheese>>,
+) {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
+        return;
+    };
+    for (entity, motion) in query.iter() {
+        let MotionPattern::Release { distance, speed } = motion.pattern else {
+            continue;
+        };
+        // the cheese rolls towards +Z, so it approaches from behind
+        let gap = motion.origin.translation.z - cheese_transform.translation.z;
+        if gap > 0. && gap < distance {
+            let downhill = motion.origin.rotation * Vec3::Z;
+            commands
+                .entity(entity)
+                .insert((RigidBody::Dynamic, LinearVelocity(downhill * speed)))
+                .remove::<ObstacleMotion>();
+        }
+    }
+}
+
+// how much of its speed the cheese keeps after smashing through an obstacle
+const SPEED_KEPT_AFTER_BREAK: f32 = 0.6;
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn break_obstacles(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    contacts: Res<Collisions>,
+    mut cheese_query: Query<
+        (
+            Entity,
+            &PreviousVelocity,
+            &Mass,
+            &Rotation,
+            &mut LinearVelocity,
+        ),
+        With<Cheese>,
+    >,
+    obstacle_query: Query<(&Obstacle, &GlobalTransform, Option<&LinearVelocity>), Without<Cheese>>,
+    parent_query: Query<&Parent>,
+    mut obstacles_query: Query<(&mut Obstacles, &Level)>,
+    terrain_noise: Res<TerrainNoise>,
+    seed: Res<RaceSeed>,
+    textures: Res<TextureAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Ok((cheese, previous_velocity, mass, rotation, mut velocity)) =
+        cheese_query.get_single_mut()
+    else {
+        return;
+    };
+    let Ok((mut obstacles, level)) = obstacles_query.get_single_mut() else {
+        return;
+    };
+    let mut broken = vec![];
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let other = if *entity1 == cheese {
+            *entity2
+        } else if *entity2 == cheese {
+            *entity1
+        } else {
+            continue;
+        };
+        // obstacle colliders are children of the obstacle's body
+        let obstacle_entity = parent_query.get(other).map_or(other, |parent| parent.get());
+        if broken.contains(&obstacle_entity) {
+            continue;
+        }
+        let Ok((obstacle, transform, obstacle_velocity)) = obstacle_query.get(obstacle_entity)
+        else {
+            continue;
+        };
+        let Some(break_impulse) = obstacle.kind.break_impulse() else {
+            continue;
+        };
+        let Some(contacts) = contacts.get(cheese, other) else {
+            continue;
+        };
+        // moving obstacles are kinematic, so their velocity isn't changed by the contact
+        let relative_velocity =
+            previous_velocity.0 - obstacle_velocity.map_or(Vec3::ZERO, |velocity| velocity.0);
+        // the impulse it would take to stop the cheese heading into the obstacle
+        if mass.0 * impact_speed(contacts, cheese, rotation, relative_velocity) < break_impulse {
+            continue;
+        }
+
+        let debris = Debris::shatter(
+            obstacle,
+            transform,
+            previous_velocity.0,
+            seed.0,
             level,
-            &noise.get(),
+            &terrain_noise.get(),
             &mut commands,
             &textures,
             &mut meshes,
             &mut materials,
         );
+        obstacles.replace_entity(obstacle_entity, debris);
+        commands.entity(obstacle_entity).despawn_recursive();
+        broken.push(obstacle_entity);
+        velocity.0 = previous_velocity.0 * SPEED_KEPT_AFTER_BREAK;
+    }
+}
+
+pub(super) fn expire_debris(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Debris)>,
+    time: Res<Time>,
+) {
+    for (entity, mut debris) in query.iter_mut() {
+        if debris.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
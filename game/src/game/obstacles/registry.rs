@@ -0,0 +1,178 @@
+use rand::Rng;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use super::ObstacleKind;
+
+// The kinds of hill that a race can take place on, each with its own mix of obstacles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Resource, Reflect)]
+pub enum Biome {
+    #[default]
+    Hillside,
+    Farmland,
+    Woodland,
+    Quarry,
+}
+
+impl Biome {
+    pub const ALL: [Biome; 4] = [
+        Biome::Hillside,
+        Biome::Farmland,
+        Biome::Woodland,
+        Biome::Quarry,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Biome::Hillside => "Hillside",
+            Biome::Farmland => "Farmland",
+            Biome::Woodland => "Woodland",
+            Biome::Quarry => "Quarry",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|biome| biome == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub struct ObstacleEntry {
+    pub kind: ObstacleKind,
+    // relative to the other entries in the registry
+    pub weight: u32,
+    // the first obstacle chunk (counting down the hill) that the kind may appear in
+    pub min_chunk: i32,
+}
+
+impl ObstacleEntry {
+    pub fn new(kind: ObstacleKind, weight: u32) -> Self {
+        Self {
+            kind,
+            weight,
+            min_chunk: 0,
+        }
+    }
+
+    pub fn with_min_chunk(mut self, min_chunk: i32) -> Self {
+        self.min_chunk = min_chunk;
+        self
+    }
+}
+
+// Decides which kinds of obstacle are generated on the hill and how often each one appears.
+// Picking a biome on the main menu inserts that biome's registry; insert a different registry
+// before the race starts to change the mix.
+#[derive(Clone, Debug)]
+#[derive(Resource, Reflect)]
+pub struct ObstacleRegistry {
+    pub entries: Vec<ObstacleEntry>,
+}
+
+impl Default for ObstacleRegistry {
+    fn default() -> Self {
+        Self::for_biome(Biome::default())
+    }
+}
+
+impl ObstacleRegistry {
+    pub fn new(entries: Vec<ObstacleEntry>) -> Self {
+        Self { entries }
+    }
+
+    pub fn for_biome(biome: Biome) -> Self {
+        let wall = ObstacleKind::Wall { width: 20 };
+        let fence = ObstacleKind::Fence { width: 12 };
+        Self::new(match biome {
+            Biome::Hillside => vec![
+                ObstacleEntry::new(wall, 6),
+                ObstacleEntry::new(fence, 3),
+                ObstacleEntry::new(ObstacleKind::HayBale, 2),
+                ObstacleEntry::new(ObstacleKind::Stump, 2),
+                ObstacleEntry::new(ObstacleKind::Boulder, 2).with_min_chunk(2),
+                ObstacleEntry::new(ObstacleKind::RollingLog, 1).with_min_chunk(4),
+                ObstacleEntry::new(ObstacleKind::SwingingGate, 1).with_min_chunk(3),
+                ObstacleEntry::new(ObstacleKind::BarrelDrop, 1).with_min_chunk(5),
+            ],
+            Biome::Farmland => vec![
+                ObstacleEntry::new(fence, 6),
+                ObstacleEntry::new(ObstacleKind::HayBale, 6),
+                ObstacleEntry::new(wall, 2),
+                ObstacleEntry::new(ObstacleKind::Post, 1),
+                ObstacleEntry::new(ObstacleKind::SwingingGate, 2).with_min_chunk(2),
+                ObstacleEntry::new(ObstacleKind::Tractor, 2).with_min_chunk(3),
+            ],
+            Biome::Woodland => vec![
+                ObstacleEntry::new(ObstacleKind::Stump, 6),
+                ObstacleEntry::new(ObstacleKind::RollingLog, 3).with_min_chunk(2),
+                ObstacleEntry::new(ObstacleKind::Boulder, 2),
+                ObstacleEntry::new(fence, 1),
+                ObstacleEntry::new(ObstacleKind::BarrelDrop, 1).with_min_chunk(4),
+            ],
+            Biome::Quarry => vec![
+                ObstacleEntry::new(ObstacleKind::Boulder, 6),
+                ObstacleEntry::new(wall, 3),
+                ObstacleEntry::new(ObstacleKind::Pillar, 2),
+                ObstacleEntry::new(ObstacleKind::Post, 1),
+                ObstacleEntry::new(ObstacleKind::SlidingBarrier, 3).with_min_chunk(2),
+            ],
+        })
+    }
+
+    // change how often a kind appears, adding it to the registry if it is missing
+    pub fn with_weight(mut self, kind: ObstacleKind, weight: u32) -> Self {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.kind == kind) {
+            entry.weight = weight;
+        } else {
+            self.entries.push(ObstacleEntry::new(kind, weight));
+        }
+        self
+    }
+
+    pub fn without(mut self, kind: ObstacleKind) -> Self {
+        self.entries.retain(|entry| entry.kind != kind);
+        self
+    }
+
+    // picks a kind for the obstacle chunk at chunk_z, weighted by the entries that allow it.
+    // the weights of obstacles that move are scaled by moving_weight.
+    pub fn choose(
+        &self,
+        rng: &mut impl Rng,
+        chunk_z: i32,
+        moving_weight: f32,
+    ) -> Option<ObstacleKind> {
+        let weight = |entry: &ObstacleEntry| {
+            if entry.min_chunk > chunk_z {
+                0.
+            } else if entry.kind.rigid_body() == RigidBody::Static {
+                entry.weight as f32
+            } else {
+                entry.weight as f32 * moving_weight.max(0.)
+            }
+        };
+        let total_weight: f32 = self.entries.iter().map(weight).sum();
+        if total_weight <= 0. {
+            return None;
+        }
+        let mut roll = rng.gen_range(0.0..total_weight);
+        for entry in self.entries.iter() {
+            let weight = weight(entry);
+            if weight <= 0. {
+                continue;
+            }
+            if roll < weight {
+                return Some(entry.kind);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+
+// How a person runs, balances and grabs, tuned per `Person`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub struct PersonBehaviour {
+    // how hard the torso is held upright, and how much that is damped
+    pub balance_stiffness: f32,
+    pub balance_damping: f32,
+    // how far the torso leans into the run, in radians
+    pub lean: f32,
+    // how far each leg swings either side of straight down, in radians
+    pub stride: f32,
+    // strides per second
+    pub cadence: f32,
+    // how far the arms flail with each stride, in radians
+    pub arm_swing: f32,
+    pub limb_stiffness: f32,
+    pub limb_damping: f32,
+    // the cheese has to be this close before the arms reach for it instead of flailing
+    pub reach_distance: f32,
+    // how many seconds a person lies on the ground before trying to get up
+    pub get_up_delay: f32,
+}
+
+impl Default for PersonBehaviour {
+    fn default() -> Self {
+        Self {
+            balance_stiffness: 60.,
+            balance_damping: 8.,
+            lean: 0.25,
+            stride: 0.7,
+            cadence: 1.5,
+            arm_swing: 1.2,
+            limb_stiffness: 120.,
+            limb_damping: 10.,
+            reach_distance: 12.,
+            get_up_delay: 1.,
+        }
+    }
+}
+
+// How the muscles swing a limb
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LimbMotion {
+    // back and forth with each stride, like a leg
+    Stride,
+    // back and forth with each stride until the cheese is close enough to reach, like an arm
+    Reach,
+}
+
+// A limb that the muscles drive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LimbDrive {
+    pub motion: LimbMotion,
+    // the direction from the limb's joint to its far end, in the limb's own frame
+    pub axis: Vec3,
+    // how far the limb's swing is out of step with the gait, in radians
+    pub phase: f32,
+}
+
+impl LimbDrive {
+    pub fn new(motion: LimbMotion, axis: Vec3, phase: f32) -> Self {
+        Self {
+            motion,
+            axis,
+            phase,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DrivenLimb {
+    pub entity: Entity,
+    pub drive: LimbDrive,
+}
+
+// The body parts of a ragdoll, so that they can be driven from the torso
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct RagdollLimbs {
+    // every body part but the torso
+    pub parts: Vec<Entity>,
+    pub driven: Vec<DrivenLimb>,
+}
+
+impl RagdollLimbs {
+    pub fn all(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.parts.iter().copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Reflect)]
+pub enum RagdollPose {
+    #[default]
+    Running,
+    // lying where it landed, catching its breath
+    Fallen,
+    // pushing itself back upright
+    GettingUp,
+}
+
+// The state of a ragdoll's muscles
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+pub struct ActiveRagdoll {
+    pub pose: RagdollPose,
+    // where the legs are in their stride, in radians
+    pub gait_phase: f32,
+    // seconds spent in the current pose
+    pub pose_time: f32,
+}
+
+impl ActiveRagdoll {
+    // the torso counts as fallen over once its up is this far from straight up
+    pub const FALLEN_ALIGNMENT: f32 = 0.3;
+    pub const UPRIGHT_ALIGNMENT: f32 = 0.8;
+    // getting up braces the torso this much harder than running does
+    pub const GET_UP_BOOST: f32 = 3.;
+
+    pub fn set_pose(&mut self, pose: RagdollPose) {
+        if self.pose != pose {
+            self.pose = pose;
+            self.pose_time = 0.;
+        }
+    }
+
+    // moves between running, falling and getting up from how upright the torso is
+    pub fn update_pose(&mut self, alignment: f32, behaviour: &PersonBehaviour, delta: f32) {
+        self.pose_time += delta;
+        match self.pose {
+            RagdollPose::Running if alignment < Self::FALLEN_ALIGNMENT => {
+                self.set_pose(RagdollPose::Fallen);
+            }
+            RagdollPose::Fallen if self.pose_time >= behaviour.get_up_delay => {
+                self.set_pose(RagdollPose::GettingUp);
+            }
+            RagdollPose::Fallen | RagdollPose::GettingUp
+                if alignment >= Self::UPRIGHT_ALIGNMENT =>
+            {
+                self.set_pose(RagdollPose::Running);
+            }
+            _ => {}
+        }
+        if self.pose == RagdollPose::Running {
+            self.gait_phase = (self.gait_phase + behaviour.cadence * std::f32::consts::TAU * delta)
+                % std::f32::consts::TAU;
+        }
+    }
+
+    // how strongly the torso is held upright in the current pose
+    pub fn balance_scale(&self) -> f32 {
+        match self.pose {
+            RagdollPose::Running => 1.,
+            // fallen people go limp
+            RagdollPose::Fallen => 0.,
+            RagdollPose::GettingUp => Self::GET_UP_BOOST,
+        }
+    }
+}
+
+// The angular impulse that pushes a body from `current` towards `target`, as a PD controller.
+// `world_inertia` is the body's inertia tensor in world space, so the gains are the same for
+// every size of body
+pub fn pd_angular_impulse(
+    current: Quat,
+    target: Quat,
+    relative_angular_velocity: Vec3,
+    stiffness: f32,
+    damping: f32,
+    world_inertia: Mat3,
+    delta: f32,
+) -> Vec3 {
+    let mut error = target * current.inverse();
+    // take the short way around
+    if error.w < 0. {
+        error = -error;
+    }
+    let (axis, angle) = error.to_axis_angle();
+    let acceleration = axis * angle * stiffness - relative_angular_velocity * damping;
+    let impulse = world_inertia * acceleration * delta;
+    if impulse.is_finite() {
+        impulse
+    } else {
+        Vec3::ZERO
+    }
+}
+
+// the inertia tensor of a body rotated into world space
+pub fn world_inertia(local_inertia: Mat3, rotation: Quat) -> Mat3 {
+    let rotation = Mat3::from_quat(rotation);
+    rotation * local_inertia * rotation.transpose()
+}
@@ -0,0 +1,263 @@
+use rand::Rng;
+
+use bevy::prelude::*;
+
+use super::PersonBehaviour;
+
+// The build of a pursuer, which changes how heavy and bendy they are and how hard they chase
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum PersonArchetype {
+    #[default]
+    Regular,
+    // light and quick, with long strides
+    Sprinter,
+    // slow and stiff, but hard to knock over
+    Heavy,
+    // small and floppy
+    Child,
+    // on four legs, light and the fastest of all
+    Dog,
+}
+
+impl PersonArchetype {
+    pub const ALL: [PersonArchetype; 5] = [
+        PersonArchetype::Regular,
+        PersonArchetype::Sprinter,
+        PersonArchetype::Heavy,
+        PersonArchetype::Child,
+        PersonArchetype::Dog,
+    ];
+
+    // how often the lakitu picks each archetype, relative to the others
+    pub fn weight(&self) -> u32 {
+        match self {
+            PersonArchetype::Regular => 5,
+            PersonArchetype::Sprinter => 2,
+            PersonArchetype::Heavy => 2,
+            PersonArchetype::Child => 1,
+            PersonArchetype::Dog => 1,
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let total = Self::ALL.iter().map(Self::weight).sum::<u32>();
+        let mut roll = rng.gen_range(0..total);
+        for archetype in Self::ALL {
+            if roll < archetype.weight() {
+                return archetype;
+            }
+            roll -= archetype.weight();
+        }
+        Self::default()
+    }
+
+    // a size and girth for this build, in the same fifths as the lakitu has always used
+    pub fn random_build(&self, rng: &mut impl Rng) -> (f32, f32) {
+        let mut fifths =
+            |range: std::ops::RangeInclusive<u32>| 1.5 + rng.gen_range(range) as f32 / 5.;
+        match self {
+            PersonArchetype::Regular => (fifths(1..=10), fifths(1..=10)),
+            PersonArchetype::Sprinter => (fifths(4..=10), fifths(1..=4)),
+            PersonArchetype::Heavy => (fifths(3..=10), fifths(6..=10)),
+            PersonArchetype::Child => (fifths(1..=3), fifths(1..=3)),
+            PersonArchetype::Dog => (fifths(1..=4), fifths(1..=4)),
+        }
+    }
+
+    pub fn body_density_scale(&self) -> f32 {
+        match self {
+            PersonArchetype::Regular => 1.,
+            PersonArchetype::Sprinter => 0.7,
+            PersonArchetype::Heavy => 1.6,
+            PersonArchetype::Child => 0.8,
+            PersonArchetype::Dog => 0.5,
+        }
+    }
+
+    pub fn limb_density_scale(&self) -> f32 {
+        match self {
+            PersonArchetype::Regular => 1.,
+            PersonArchetype::Sprinter => 0.8,
+            PersonArchetype::Heavy => 1.4,
+            PersonArchetype::Child => 0.6,
+            PersonArchetype::Dog => 0.5,
+        }
+    }
+
+    // how far the neck, shoulders and hips swing either way, in radians
+    pub fn joint_limit(&self) -> f32 {
+        match self {
+            PersonArchetype::Regular => std::f32::consts::FRAC_PI_3,
+            PersonArchetype::Sprinter => std::f32::consts::FRAC_PI_2 * 0.8,
+            PersonArchetype::Heavy => std::f32::consts::FRAC_PI_4,
+            PersonArchetype::Child => std::f32::consts::FRAC_PI_2,
+            PersonArchetype::Dog => std::f32::consts::FRAC_PI_4,
+        }
+    }
+
+    // scales how strongly `chase_cheese` pulls the person in
+    pub fn pull_scale(&self) -> f32 {
+        match self {
+            PersonArchetype::Regular => 1.,
+            PersonArchetype::Sprinter => 1.3,
+            PersonArchetype::Heavy => 1.4,
+            PersonArchetype::Child => 0.8,
+            PersonArchetype::Dog => 1.6,
+        }
+    }
+
+    pub fn behaviour(&self) -> PersonBehaviour {
+        let regular = PersonBehaviour::default();
+        match self {
+            PersonArchetype::Regular => regular,
+            PersonArchetype::Sprinter => PersonBehaviour {
+                lean: 0.35,
+                stride: 0.9,
+                cadence: 2.2,
+                ..regular
+            },
+            PersonArchetype::Heavy => PersonBehaviour {
+                balance_stiffness: 80.,
+                cadence: 1.1,
+                arm_swing: 0.8,
+                get_up_delay: 1.5,
+                ..regular
+            },
+            PersonArchetype::Child => PersonBehaviour {
+                cadence: 2.,
+                stride: 0.5,
+                reach_distance: 8.,
+                get_up_delay: 0.5,
+                ..regular
+            },
+            // dogs run level, with quick short strides
+            PersonArchetype::Dog => PersonBehaviour {
+                lean: 0.05,
+                stride: 0.5,
+                cadence: 3.,
+                reach_distance: 6.,
+                get_up_delay: 0.4,
+                ..regular
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum Hat {
+    TopHat,
+    Cap,
+}
+
+impl Hat {
+    pub const ALL: [Hat; 2] = [Hat::TopHat, Hat::Cap];
+
+    pub fn color(&self) -> Color {
+        match self {
+            Hat::TopHat => Color::rgb(0.08, 0.08, 0.08),
+            Hat::Cap => Color::rgb(0.2, 0.5, 0.25),
+        }
+    }
+
+    // the radius and height of the hat, for a head of `head_radius`
+    pub fn dimensions(&self, head_radius: f32) -> (f32, f32) {
+        match self {
+            Hat::TopHat => (head_radius * 0.8, head_radius * 1.4),
+            Hat::Cap => (head_radius * 1.05, head_radius * 0.35),
+        }
+    }
+
+    pub fn mesh(&self, head_radius: f32) -> Mesh {
+        let (radius, height) = self.dimensions(head_radius);
+        shape::Cylinder {
+            radius,
+            height,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    // where the hat sits relative to the middle of a head of `head_radius`
+    pub fn offset(&self, head_radius: f32) -> Vec3 {
+        let (_, height) = self.dimensions(head_radius);
+        Vec3::Y * (head_radius * 0.8 + height * 0.5)
+    }
+}
+
+// What a person wears. The colours come from small palettes, so that ragdolls can share materials
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Reflect)]
+pub struct PersonAppearance {
+    pub shirt: Color,
+    pub trousers: Color,
+    pub skin: Color,
+    pub hat: Option<Hat>,
+}
+
+impl Default for PersonAppearance {
+    fn default() -> Self {
+        Self {
+            shirt: Self::SHIRTS[0],
+            trousers: Self::TROUSERS[0],
+            skin: Self::SKIN[0],
+            hat: None,
+        }
+    }
+}
+
+impl PersonAppearance {
+    pub const SHIRTS: [Color; 6] = [
+        Color::RED,
+        Color::rgb(0.15, 0.3, 0.8),
+        Color::rgb(0.2, 0.6, 0.2),
+        Color::rgb(0.9, 0.8, 0.2),
+        Color::rgb(0.9, 0.9, 0.9),
+        Color::rgb(0.95, 0.5, 0.1),
+    ];
+    pub const TROUSERS: [Color; 4] = [
+        Color::rgb(0.1, 0.1, 0.35),
+        Color::rgb(0.35, 0.22, 0.12),
+        Color::rgb(0.2, 0.2, 0.2),
+        Color::rgb(0.75, 0.68, 0.5),
+    ];
+    pub const SKIN: [Color; 4] = [
+        Color::rgb(0.96, 0.8, 0.69),
+        Color::rgb(0.87, 0.66, 0.5),
+        Color::rgb(0.6, 0.42, 0.3),
+        Color::rgb(0.36, 0.24, 0.17),
+    ];
+    pub const FUR: [Color; 4] = [
+        Color::rgb(0.45, 0.3, 0.15),
+        Color::rgb(0.12, 0.1, 0.08),
+        Color::rgb(0.85, 0.75, 0.55),
+        Color::rgb(0.95, 0.95, 0.92),
+    ];
+    // one in this many people wears a hat
+    const HAT_CHANCE: u32 = 3;
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            shirt: Self::SHIRTS[rng.gen_range(0..Self::SHIRTS.len())],
+            trousers: Self::TROUSERS[rng.gen_range(0..Self::TROUSERS.len())],
+            skin: Self::SKIN[rng.gen_range(0..Self::SKIN.len())],
+            hat: if rng.gen_range(0..Self::HAT_CHANCE) == 0 {
+                Some(Hat::ALL[rng.gen_range(0..Hat::ALL.len())])
+            } else {
+                None
+            },
+        }
+    }
+
+    // dogs are drawn all in their fur, and don't wear hats
+    pub fn random_fur(rng: &mut impl Rng) -> Self {
+        let fur = Self::FUR[rng.gen_range(0..Self::FUR.len())];
+        Self {
+            shirt: fur,
+            trousers: fur,
+            skin: fur,
+            hat: None,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use super::{Bone, BoneJoint, BonePaint, BoneShape, LimbDrive, LimbMotion, Person, Skeleton};
+
+impl Skeleton {
+    const DOG_BODY_LENGTH: f32 = 0.45;
+    const DOG_BODY_WIDTH: f32 = 0.2;
+    const DOG_BODY_HEIGHT: f32 = 0.22;
+    const DOG_HEAD_RADIUS: f32 = 0.09;
+    const DOG_LEG_LENGTH: f32 = 0.3;
+    const DOG_LEG_RADIUS: f32 = 0.045;
+    const DOG_TAIL_LENGTH: f32 = 0.25;
+    const DOG_TAIL_RADIUS: f32 = 0.025;
+
+    // a dog on four legs, trotting with its diagonal legs in step, that grabs with its snout
+    pub fn dog(person: &Person) -> Self {
+        let body = Vec3::new(
+            Self::DOG_BODY_WIDTH * person.girth,
+            Self::DOG_BODY_HEIGHT * person.girth,
+            Self::DOG_BODY_LENGTH * person.size,
+        );
+        let head_radius = Self::DOG_HEAD_RADIUS * person.girth;
+        let leg_radius = Self::DOG_LEG_RADIUS * person.girth;
+        let leg_length = Self::DOG_LEG_LENGTH * person.size - leg_radius * 2.;
+        let tail_radius = Self::DOG_TAIL_RADIUS * person.girth;
+        let tail_length = Self::DOG_TAIL_LENGTH * person.size;
+        let snout = Vec3::new(head_radius, head_radius * 0.8, head_radius * 1.4);
+
+        let archetype = person.archetype();
+        let body_density = person.body_density();
+        let limb_density = Person::LIMB_MASS_DENSITY * archetype.limb_density_scale();
+        let joint_limit = archetype.joint_limit();
+
+        let leg = BoneShape::Capsule {
+            length: leg_length,
+            radius: leg_radius,
+        };
+        // the top of each leg sits under a corner of the body
+        let hip = |x: f32, z: f32| {
+            Vec3::new(
+                x * (body.x / 2. - leg_radius),
+                -body.y / 2.,
+                z * (body.z / 2. - leg_radius * 2.),
+            )
+        };
+        let leg_top = Vec3::Y * (leg_length / 2. + leg_radius);
+        let legs = [
+            ("Front Left Leg", -1., 1., 0.),
+            ("Front Right Leg", 1., 1., PI),
+            ("Back Left Leg", -1., -1., PI),
+            ("Back Right Leg", 1., -1., 0.),
+        ];
+
+        let neck = Vec3::new(0., body.y / 4., body.z / 2.);
+        let head_center = neck + Vec3::new(0., head_radius * 0.5, head_radius);
+        let tail_base = Vec3::new(0., body.y / 4., -body.z / 2.);
+        // the tail sticks up and back
+        let tail_rotation = Quat::from_rotation_x(-PI / 4.);
+        let tail_end = tail_rotation * Vec3::NEG_Y * (tail_length / 2. + tail_radius);
+
+        let mut bones = legs
+            .iter()
+            .map(|&(name, x, z, phase)| {
+                Bone::new(name, leg, BonePaint::Skin, limb_density)
+                    .at(Transform::from_translation(hip(x, z) - leg_top))
+                    .driven(LimbDrive::new(LimbMotion::Stride, Vec3::NEG_Y, phase))
+            })
+            .collect::<Vec<_>>();
+        let mut joints = legs
+            .iter()
+            .enumerate()
+            .map(|(index, &(_, x, z, _))| {
+                BoneJoint::ball("Hip Joint", None, index, (hip(x, z), leg_top), joint_limit)
+            })
+            .collect::<Vec<_>>();
+
+        let head = bones.len();
+        bones.push(
+            Bone::new(
+                "Head",
+                BoneShape::Ball {
+                    radius: head_radius,
+                },
+                BonePaint::Skin,
+                body_density,
+            )
+            .at(Transform::from_translation(head_center)),
+        );
+        joints.push(
+            BoneJoint::ball(
+                "Neck Joint",
+                None,
+                head,
+                (neck, neck - head_center),
+                joint_limit,
+            )
+            .with_axes(Vec3::X, Vec3::Z),
+        );
+
+        let snout_offset = Vec3::new(0., -head_radius * 0.3, head_radius + snout.z / 2.);
+        bones.push(
+            Bone::new(
+                "Snout",
+                BoneShape::Box { size: snout },
+                BonePaint::Skin,
+                limb_density,
+            )
+            .at(Transform::from_translation(head_center + snout_offset))
+            .grabbing(),
+        );
+        joints.push(BoneJoint::fixed(
+            "Snout Joint",
+            Some(head),
+            head + 1,
+            (snout_offset, Vec3::ZERO),
+        ));
+
+        bones.push(
+            Bone::new(
+                "Tail",
+                BoneShape::Capsule {
+                    length: tail_length,
+                    radius: tail_radius,
+                },
+                BonePaint::Skin,
+                limb_density,
+            )
+            .at(Transform::from_translation(tail_base - tail_end).with_rotation(tail_rotation)),
+        );
+        joints.push(BoneJoint::ball(
+            "Tail Joint",
+            None,
+            head + 2,
+            (tail_base, tail_rotation.inverse() * tail_end),
+            joint_limit,
+        ));
+
+        Self {
+            name: "Dog",
+            torso: BoneShape::Box { size: body },
+            torso_paint: BonePaint::Skin,
+            torso_density: body_density,
+            bones,
+            joints,
+        }
+    }
+}
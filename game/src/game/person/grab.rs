@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+// A hand holding on to the cheese
+#[derive(Clone, Copy, Debug)]
+pub struct Grabber {
+    pub hand: Entity,
+    pub person: Entity,
+    // the joint that ties the hand to the cheese
+    pub joint: Entity,
+}
+
+// Which way the player last pushed while struggling
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StruggleSide {
+    Left,
+    Right,
+}
+
+// The hands holding the cheese, and how close the player is to shaking them off
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct CheeseGrip {
+    pub grabbers: Vec<Grabber>,
+    // runs from the first grab; the cheese is caught if it finishes before the escape
+    pub timer: Timer,
+    pub escape_progress: f32,
+    pub(super) last_side: Option<StruggleSide>,
+}
+
+impl CheeseGrip {
+    // seconds the player has to escape
+    pub const GRIP_TIME: f32 = 2.5;
+    // progress from a push in the other direction to the last one, and from the same direction
+    pub const ALTERNATING_PUSH: f32 = 0.12;
+    pub const REPEATED_PUSH: f32 = 0.05;
+    // each hand after the first adds this much to the escape
+    pub const EXTRA_GRABBER_EFFORT: f32 = 0.5;
+    // how hard the escape flings grabbers away from the cheese
+    pub const ESCAPE_SPEED: f32 = 8.;
+
+    pub fn is_held(&self) -> bool {
+        !self.grabbers.is_empty()
+    }
+
+    pub fn holds(&self, hand: Entity) -> bool {
+        self.grabbers.iter().any(|grabber| grabber.hand == hand)
+    }
+
+    // how much struggling it takes to escape all of the current grabbers
+    pub fn escape_effort(&self) -> f32 {
+        1. + self.grabbers.len().saturating_sub(1) as f32 * Self::EXTRA_GRABBER_EFFORT
+    }
+
+    // from 0 when freshly grabbed to 1 when the player breaks free
+    pub fn escape_fraction(&self) -> f32 {
+        (self.escape_progress / self.escape_effort()).min(1.)
+    }
+
+    pub fn add(&mut self, grabber: Grabber) {
+        if !self.is_held() {
+            self.timer = Timer::from_seconds(Self::GRIP_TIME, TimerMode::Once);
+            self.escape_progress = 0.;
+            self.last_side = None;
+        }
+        self.grabbers.push(grabber);
+    }
+
+    pub fn struggle(&mut self, side: StruggleSide) {
+        self.escape_progress += if self.last_side == Some(side) {
+            Self::REPEATED_PUSH
+        } else {
+            Self::ALTERNATING_PUSH
+        };
+        self.last_side = Some(side);
+    }
+
+    // lets go of everything, handing back the grabbers so their joints can be removed
+    pub fn release(&mut self) -> Vec<Grabber> {
+        self.escape_progress = 0.;
+        self.last_side = None;
+        std::mem::take(&mut self.grabbers)
+    }
+}
+
+// Keeps a person who was just shaken off from grabbing again straight away
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct GrabCooldown(pub Timer);
+
+impl GrabCooldown {
+    pub const SECONDS: f32 = 2.;
+}
+
+impl Default for GrabCooldown {
+    fn default() -> Self {
+        Self(Timer::from_seconds(Self::SECONDS, TimerMode::Once))
+    }
+}
@@ -0,0 +1,183 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use super::{Bone, BoneJoint, BonePaint, BoneShape, LimbDrive, LimbMotion, Person, Skeleton};
+
+impl Skeleton {
+    const HUMAN_HEAD_RADIUS: f32 = 0.09;
+
+    const HUMAN_TORSO_LENGTH: f32 = 0.5;
+    const HUMAN_TORSO_RADIUS: f32 = 0.1;
+
+    const HUMAN_LIMB_LENGTH: f32 = 0.6;
+    const HUMAN_LIMB_RADIUS: f32 = 0.05;
+    const HUMAN_ARM_TO_LEG_RATIO: f32 = 0.75;
+
+    // a person on two legs, striding with their arms swinging against their legs, who grabs with
+    // their hands and may wear a hat
+    pub fn human(person: &Person) -> Self {
+        let head_radius = Self::HUMAN_HEAD_RADIUS * person.girth;
+        let torso_radius = Self::HUMAN_TORSO_RADIUS * person.girth;
+        let torso_height = Self::HUMAN_TORSO_LENGTH * person.size - torso_radius * 2.;
+        let limb_radius = Self::HUMAN_LIMB_RADIUS * person.girth;
+        let arm_height =
+            Self::HUMAN_LIMB_LENGTH * person.size * Self::HUMAN_ARM_TO_LEG_RATIO - limb_radius * 2.;
+        let hand_size = Self::HUMAN_LIMB_RADIUS * 1.1;
+        let leg_height = Self::HUMAN_LIMB_LENGTH * person.size - limb_radius * 2.;
+
+        let archetype = person.archetype();
+        let body_density = person.body_density();
+        let limb_density = Person::LIMB_MASS_DENSITY * archetype.limb_density_scale();
+        let joint_limit = archetype.joint_limit();
+
+        let arm = BoneShape::Capsule {
+            length: arm_height,
+            radius: limb_radius,
+        };
+        let hand = BoneShape::Ball { radius: hand_size };
+        let leg = BoneShape::Capsule {
+            length: leg_height,
+            radius: limb_radius,
+        };
+        let shoulder_y = torso_height / 2. - torso_radius * 0.4;
+        let arm_end = arm_height / 2. + limb_radius;
+        let leg_y = -torso_height / 2. - torso_radius - (leg_height + limb_radius * 2.) / 2.;
+        let hip = |x: f32| {
+            Vec3::new(
+                x * torso_radius / 1.7,
+                -torso_height / 2. - torso_radius / 1.7,
+                0.,
+            )
+        };
+
+        // the legs stride in opposition, and the arms swing against the legs. the left arm's hand
+        // hangs off its -Y end and the right arm's off its +Y end
+        let bones = vec![
+            Bone::new(
+                "Head",
+                BoneShape::Ball {
+                    radius: head_radius,
+                },
+                BonePaint::Skin,
+                body_density,
+            )
+            .at(Transform::from_translation(
+                Vec3::Y * (torso_height * 0.5 + torso_radius + head_radius * 0.5),
+            ))
+            .wearing_hat(),
+            Bone::new("Left Arm", arm, BonePaint::Shirt, limb_density)
+                .at(Transform::from_xyz(
+                    -torso_radius - limb_radius * 2.,
+                    shoulder_y,
+                    0.,
+                ))
+                .driven(LimbDrive::new(LimbMotion::Reach, Vec3::NEG_Y, PI)),
+            Bone::new("Right Arm", arm, BonePaint::Shirt, limb_density)
+                .at(Transform::from_xyz(
+                    torso_radius + limb_radius * 2.,
+                    shoulder_y,
+                    0.,
+                ))
+                .driven(LimbDrive::new(LimbMotion::Reach, Vec3::Y, 0.)),
+            Bone::new("Left Hand", hand, BonePaint::Skin, limb_density)
+                .at(Transform::from_xyz(
+                    -torso_radius - arm_height - limb_radius * 2.,
+                    shoulder_y,
+                    0.,
+                ))
+                .grabbing(),
+            Bone::new("Right Hand", hand, BonePaint::Skin, limb_density)
+                .at(Transform::from_xyz(
+                    torso_radius + arm_height + limb_radius * 2.,
+                    shoulder_y,
+                    0.,
+                ))
+                .grabbing(),
+            Bone::new("Left Leg", leg, BonePaint::Trousers, limb_density)
+                .at(Transform::from_xyz(
+                    -torso_radius + limb_radius * 0.8,
+                    leg_y,
+                    0.,
+                ))
+                .driven(LimbDrive::new(LimbMotion::Stride, Vec3::NEG_Y, 0.)),
+            Bone::new("Right Leg", leg, BonePaint::Trousers, limb_density)
+                .at(Transform::from_xyz(
+                    torso_radius - limb_radius * 0.8,
+                    leg_y,
+                    0.,
+                ))
+                .driven(LimbDrive::new(LimbMotion::Stride, Vec3::NEG_Y, PI)),
+        ];
+
+        let joints = vec![
+            BoneJoint::ball(
+                "Neck Joint",
+                None,
+                0,
+                (Vec3::Y * torso_height, Vec3::NEG_Y * head_radius * 0.4),
+                joint_limit,
+            )
+            .with_axes(Vec3::X, Vec3::Z),
+            BoneJoint::ball(
+                "Left Shoulder Joint",
+                None,
+                1,
+                (
+                    Vec3::new(-torso_radius - limb_radius, torso_height / 2., 0.),
+                    Vec3::Y * arm_end,
+                ),
+                joint_limit,
+            )
+            .with_axes(Vec3::NEG_X, Vec3::Y),
+            BoneJoint::ball(
+                "Right Shoulder Joint",
+                None,
+                2,
+                (
+                    Vec3::new(torso_radius + limb_radius, torso_height / 2., 0.),
+                    Vec3::NEG_Y * arm_end,
+                ),
+                joint_limit,
+            ),
+            BoneJoint::fixed(
+                "Left Wrist Joint",
+                Some(1),
+                3,
+                (Vec3::NEG_Y * arm_end, Vec3::ZERO),
+            ),
+            BoneJoint::fixed(
+                "Right Wrist Joint",
+                Some(2),
+                4,
+                (Vec3::Y * arm_end, Vec3::ZERO),
+            ),
+            BoneJoint::ball(
+                "Left Hip Joint",
+                None,
+                5,
+                (hip(-1.), Vec3::Y * leg_height / 2.),
+                joint_limit,
+            ),
+            BoneJoint::ball(
+                "Right Hip Joint",
+                None,
+                6,
+                (hip(1.), Vec3::Y * leg_height / 2.),
+                joint_limit,
+            ),
+        ];
+
+        Self {
+            name: "Person",
+            torso: BoneShape::Capsule {
+                length: torso_height,
+                radius: torso_radius,
+            },
+            torso_paint: BonePaint::Shirt,
+            torso_density: body_density,
+            bones,
+            joints,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+// A person bowled over by the cheese, who lies limp and stops chasing until the timer runs out
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct KnockedDown(pub Timer);
+
+impl KnockedDown {
+    // hits that change the person's speed by less than this are shrugged off
+    pub const MIN_SPEED_CHANGE: f32 = 4.;
+    // how bouncy a hit from the cheese is
+    pub const RESTITUTION: f32 = 0.5;
+    // how much of the hit throws the person upwards as well as along
+    pub const LIFT: f32 = 0.4;
+    // seconds spent limp, from the lightest knockdown to the hardest
+    pub const MIN_LIMP_TIME: f32 = 2.;
+    pub const MAX_LIMP_TIME: f32 = 5.;
+    // seconds of limpness for each unit of speed change past the minimum
+    const LIMP_TIME_PER_SPEED: f32 = 0.15;
+
+    // how much the person's speed changes when the cheese hits them, by the impulse between the
+    // two bodies. heavier people are pushed around less
+    pub fn speed_change(relative_speed: f32, cheese_mass: f32, person_mass: f32) -> f32 {
+        let reduced_mass = cheese_mass * person_mass / (cheese_mass + person_mass);
+        let impulse = reduced_mass * relative_speed * (1. + Self::RESTITUTION);
+        impulse / person_mass
+    }
+
+    pub fn from_speed_change(speed_change: f32) -> Self {
+        let seconds = Self::MIN_LIMP_TIME
+            + (speed_change - Self::MIN_SPEED_CHANGE) * Self::LIMP_TIME_PER_SPEED;
+        Self(Timer::from_seconds(
+            seconds.clamp(Self::MIN_LIMP_TIME, Self::MAX_LIMP_TIME),
+            TimerMode::Once,
+        ))
+    }
+}
+
+// Sent when the cheese knocks a person down
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct PersonKnockedDown {
+    pub person: Entity,
+    // the impulse the person took from the hit
+    pub impulse: f32,
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+// The hot-air balloon that floats behind the cheese and drops the pursuers
+#[derive(Clone, Debug, Default)]
+#[derive(Component)]
+pub struct Lakitu {
+    // pooled ragdolls held back for the next drop, and where they drop relative to the basket
+    pub pending: Vec<(Entity, Vec3)>,
+    // runs from when a drop is announced until the ragdolls are let go
+    pub warning: Timer,
+    // seconds since the lakitu was spawned, which it bobs and sways along with
+    pub age: f32,
+}
+
+impl Lakitu {
+    // seconds between the warning and the drop
+    pub const WARNING_TIME: f32 = 1.2;
+    const ENVELOPE_RADIUS: f32 = 6.;
+    // how far the envelope floats above the basket
+    const ENVELOPE_HEIGHT: f32 = 10.;
+    const BASKET_SIZE: Vec3 = Vec3::new(3., 1.6, 3.);
+    const ROPE_THICKNESS: f32 = 0.1;
+    const BURNER_RADIUS: f32 = 0.6;
+    const BOB_HEIGHT: f32 = 0.8;
+    const BOB_RATE: f32 = 0.9;
+    const SWAY_ANGLE: f32 = 0.04;
+    const SWAY_RATE: f32 = 0.6;
+    // the shudder at the height of a warning
+    const SHUDDER_ANGLE: f32 = 0.12;
+    const SHUDDER_RATE: f32 = 14.;
+    const FLICKER_RATE: f32 = 20.;
+
+    pub fn is_warning(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    // how far through the warning the lakitu is, from 0 to 1
+    pub fn warning_fraction(&self) -> f32 {
+        if self.is_warning() {
+            self.warning.percent()
+        } else {
+            0.
+        }
+    }
+
+    pub fn warn(&mut self, pending: Vec<(Entity, Vec3)>) {
+        self.pending = pending;
+        self.warning = Timer::from_seconds(Self::WARNING_TIME, TimerMode::Once);
+    }
+
+    // hands over the pending drops once the warning has run out
+    pub fn tick(&mut self, delta: Duration) -> Option<Vec<(Entity, Vec3)>> {
+        if self.is_warning() && self.warning.tick(delta).finished() {
+            Some(std::mem::take(&mut self.pending))
+        } else {
+            None
+        }
+    }
+
+    // where the model sits relative to the lakitu: a gentle bob and sway, with a shudder that
+    // builds up to each drop
+    pub fn model_transform(&self) -> Transform {
+        let sway = (self.age * Self::SWAY_RATE).sin() * Self::SWAY_ANGLE
+            + (self.age * Self::SHUDDER_RATE).sin() * Self::SHUDDER_ANGLE * self.warning_fraction();
+        Transform::from_translation(Vec3::Y * (self.age * Self::BOB_RATE).sin() * Self::BOB_HEIGHT)
+            .with_rotation(Quat::from_rotation_z(sway))
+    }
+
+    // the burner flickers, burning hotter as the drop gets closer
+    pub fn burner_scale(&self) -> f32 {
+        1. + self.warning_fraction() * (self.age * Self::FLICKER_RATE).sin().abs()
+    }
+
+    // builds the balloon with its basket at `translation`
+    pub fn spawn(
+        translation: Vec3,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        let rope_length = Self::ENVELOPE_HEIGHT - Self::ENVELOPE_RADIUS;
+        let rope_mesh = meshes
+            .add(shape::Box::new(Self::ROPE_THICKNESS, rope_length, Self::ROPE_THICKNESS).into());
+        let rope_material = materials.add(Color::rgb(0.3, 0.2, 0.1).into());
+        let rope_corners = [(-1., -1.), (-1., 1.), (1., -1.), (1., 1.)];
+        commands
+            .spawn((
+                Name::new("Lakitu"),
+                Lakitu::default(),
+                SpatialBundle::from_transform(Transform::from_translation(translation)),
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn((
+                        Name::new("Lakitu Model"),
+                        LakituModel,
+                        SpatialBundle::default(),
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn((
+                            Name::new("Lakitu Envelope"),
+                            PbrBundle {
+                                mesh: meshes.add(
+                                    shape::UVSphere {
+                                        radius: Self::ENVELOPE_RADIUS,
+                                        ..Default::default()
+                                    }
+                                    .into(),
+                                ),
+                                material: materials.add(Color::rgb(0.85, 0.2, 0.15).into()),
+                                transform: Transform::from_translation(
+                                    Vec3::Y * Self::ENVELOPE_HEIGHT,
+                                )
+                                .with_scale(Vec3::new(1., 1.2, 1.)),
+                                ..Default::default()
+                            },
+                        ));
+                        builder.spawn((
+                            Name::new("Lakitu Basket"),
+                            PbrBundle {
+                                mesh: meshes.add(
+                                    shape::Box::new(
+                                        Self::BASKET_SIZE.x,
+                                        Self::BASKET_SIZE.y,
+                                        Self::BASKET_SIZE.z,
+                                    )
+                                    .into(),
+                                ),
+                                material: materials.add(Color::rgb(0.55, 0.4, 0.2).into()),
+                                ..Default::default()
+                            },
+                        ));
+                        // ropes from each corner of the basket up to the envelope
+                        for (x, z) in rope_corners {
+                            let corner = Vec3::new(
+                                x * Self::BASKET_SIZE.x / 2.,
+                                (Self::BASKET_SIZE.y + rope_length) / 2.,
+                                z * Self::BASKET_SIZE.z / 2.,
+                            );
+                            builder.spawn((
+                                Name::new("Lakitu Rope"),
+                                PbrBundle {
+                                    mesh: rope_mesh.clone(),
+                                    material: rope_material.clone(),
+                                    transform: Transform::from_translation(corner),
+                                    ..Default::default()
+                                },
+                            ));
+                        }
+                        builder.spawn((
+                            Name::new("Lakitu Burner"),
+                            LakituBurner,
+                            PbrBundle {
+                                mesh: meshes.add(
+                                    shape::UVSphere {
+                                        radius: Self::BURNER_RADIUS,
+                                        ..Default::default()
+                                    }
+                                    .into(),
+                                ),
+                                material: materials.add(StandardMaterial {
+                                    base_color: Color::ORANGE,
+                                    emissive: Color::ORANGE_RED,
+                                    ..Default::default()
+                                }),
+                                transform: Transform::from_translation(
+                                    Vec3::Y * (Self::BASKET_SIZE.y / 2. + rope_length / 2.),
+                                ),
+                                visibility: Visibility::Hidden,
+                                ..Default::default()
+                            },
+                        ));
+                    });
+            })
+            .id()
+    }
+}
+
+// The balloon's envelope, basket and ropes, which bob and sway apart from the lakitu's path
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct LakituModel;
+
+// The flame under the envelope, which flares up while a drop is on its way
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct LakituBurner;
+
+// Sent when the lakitu announces a drop, so that it can be heard coming
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct LakituWarning {
+    pub translation: Vec3,
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::GameLayer;
+
+use super::{Person, RagdollAssets, RagdollLimbs};
+
+// How much of a ragdoll is simulated, from most to least
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Component)]
+pub enum PhysicsLod {
+    // every body part, bumping into other ragdolls
+    #[default]
+    Full,
+    // every body part, passing through other ragdolls
+    NoRagdollContacts,
+    // one capsule, with the limbs carried along by the torso
+    Merged,
+}
+
+// How far from the cheese ragdolls are simplified
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub struct PhysicsLodSettings {
+    pub contact_distance: f32,
+    pub merge_distance: f32,
+    // how far past a threshold a ragdoll has to move before it changes level, so that ragdolls
+    // on the boundary don't flip back and forth
+    pub hysteresis: f32,
+}
+
+impl Default for PhysicsLodSettings {
+    fn default() -> Self {
+        Self {
+            contact_distance: 60.,
+            merge_distance: 150.,
+            hysteresis: 10.,
+        }
+    }
+}
+
+impl PhysicsLodSettings {
+    pub fn lod(&self, current: PhysicsLod, distance: f32) -> PhysicsLod {
+        let beyond = |threshold: f32, lod: PhysicsLod| {
+            if current >= lod {
+                distance > threshold - self.hysteresis
+            } else {
+                distance > threshold + self.hysteresis
+            }
+        };
+        if beyond(self.merge_distance, PhysicsLod::Merged) {
+            PhysicsLod::Merged
+        } else if beyond(self.contact_distance, PhysicsLod::NoRagdollContacts) {
+            PhysicsLod::NoRagdollContacts
+        } else {
+            PhysicsLod::Full
+        }
+    }
+}
+
+impl PhysicsLod {
+    // switches the ragdoll's colliders and bodies over to this level, from `previous`.
+    // limbs that are split back out of a merged ragdoll start with the torso's velocity
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        previous: PhysicsLod,
+        body: Entity,
+        person: &Person,
+        limbs: &RagdollLimbs,
+        velocity: Vec3,
+        assets: &mut RagdollAssets,
+        commands: &mut Commands,
+    ) {
+        let ragdoll_contacts = *self == PhysicsLod::Full;
+        commands
+            .entity(body)
+            .insert((*self, GameLayer::ragdoll_body(ragdoll_contacts)));
+        if *self == PhysicsLod::Merged && previous != PhysicsLod::Merged {
+            let (collider, density) = assets.merged_collider(person);
+            commands
+                .entity(body)
+                .insert((collider, ColliderDensity(density)));
+            // without a rigid body of their own, the limbs ride along as part of the torso
+            for limb in limbs.all() {
+                commands
+                    .entity(limb)
+                    .remove::<RigidBody>()
+                    .insert(CollisionLayers::none());
+            }
+        } else if *self != PhysicsLod::Merged && previous == PhysicsLod::Merged {
+            let (collider, density) = assets.torso_collider(person);
+            commands
+                .entity(body)
+                .insert((collider, ColliderDensity(density)));
+            for limb in limbs.all() {
+                commands.entity(limb).insert((
+                    RigidBody::Dynamic,
+                    GameLayer::ragdoll_limb(ragdoll_contacts),
+                    LinearVelocity(velocity),
+                    AngularVelocity::ZERO,
+                ));
+            }
+        } else if *self != PhysicsLod::Merged {
+            for limb in limbs.all() {
+                commands
+                    .entity(limb)
+                    .insert(GameLayer::ragdoll_limb(ragdoll_contacts));
+            }
+        }
+    }
+}
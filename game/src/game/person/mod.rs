@@ -1,17 +1,55 @@
+use rand::Rng;
+
 use bevy::prelude::*;
 
+use bevy_xpbd_3d::prelude::*;
+
 use crate::AppState;
 
+mod active;
+pub use active::*;
+
+mod appearance;
+pub use appearance::*;
+
+mod dog;
+
+mod grab;
+pub use grab::*;
+
+mod human;
+
+mod knockdown;
+pub use knockdown::*;
+
+mod lakitu;
+pub use lakitu::*;
+
+mod lod;
+pub use lod::*;
+
+mod pool;
+pub use pool::*;
+
+mod pursuers;
+pub use pursuers::*;
+
 mod ragdoll;
 
+mod skeleton;
+pub use skeleton::*;
+
 mod systems;
 use systems::*;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[derive(Component)]
 pub struct Person {
     size: f32,
     girth: f32,
+    archetype: PersonArchetype,
+    appearance: PersonAppearance,
+    behaviour: PersonBehaviour,
 }
 
 impl Default for Person {
@@ -22,7 +60,75 @@ impl Default for Person {
 
 impl Person {
     pub fn new(size: f32, girth: f32) -> Self {
-        Self { size, girth }
+        Self {
+            size,
+            girth,
+            archetype: PersonArchetype::default(),
+            appearance: PersonAppearance::default(),
+            behaviour: PersonBehaviour::default(),
+        }
+    }
+
+    // a person of one of the builds and sizes that the lakitu drops, in random clothes
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let archetype = PersonArchetype::random(rng);
+        let (size, girth) = archetype.random_build(rng);
+        let appearance = match archetype {
+            PersonArchetype::Dog => PersonAppearance::random_fur(rng),
+            _ => PersonAppearance::random(rng),
+        };
+        Self::new(size, girth)
+            .with_archetype(archetype)
+            .with_appearance(appearance)
+    }
+
+    pub fn giant() -> Self {
+        Self::new(PursuerSize::GIANT_SIZE, PursuerSize::GIANT_GIRTH)
+    }
+
+    // also takes on the archetype's behaviour
+    pub fn with_archetype(mut self, archetype: PersonArchetype) -> Self {
+        self.archetype = archetype;
+        self.behaviour = archetype.behaviour();
+        self
+    }
+
+    pub fn with_appearance(mut self, appearance: PersonAppearance) -> Self {
+        self.appearance = appearance;
+        self
+    }
+
+    pub fn with_behaviour(mut self, behaviour: PersonBehaviour) -> Self {
+        self.behaviour = behaviour;
+        self
+    }
+
+    pub fn behaviour(&self) -> &PersonBehaviour {
+        &self.behaviour
+    }
+
+    pub fn archetype(&self) -> PersonArchetype {
+        self.archetype
+    }
+
+    pub fn appearance(&self) -> &PersonAppearance {
+        &self.appearance
+    }
+
+    // how the person is described at the end of a race
+    pub fn describe(&self) -> &'static str {
+        if self.size >= PursuerSize::GIANT_SIZE {
+            return "a giant";
+        }
+        match self.archetype {
+            PersonArchetype::Sprinter => "a sprinter",
+            PersonArchetype::Heavy => "a heavyweight",
+            PersonArchetype::Child => "a child",
+            PersonArchetype::Dog => "a dog",
+            PersonArchetype::Regular if self.size >= 3. => "a huge ragdoll",
+            PersonArchetype::Regular if self.size <= 2. => "a little ragdoll",
+            PersonArchetype::Regular => "a ragdoll",
+        }
     }
 }
 
@@ -30,16 +136,34 @@ pub struct PersonPlugin;
 
 impl Plugin for PersonPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                chase_cheese,
-                detect_grab,
-                spawn_ragdolls,
-                loop_ragdolls,
-                despawn_infinites,
+        app.init_resource::<CheeseGrip>()
+            .init_resource::<RagdollAssets>()
+            .init_resource::<RagdollPool>()
+            .init_resource::<PursuerConfig>()
+            .init_resource::<PursuerScheduler>()
+            .init_resource::<PhysicsLodSettings>()
+            .add_event::<PersonKnockedDown>()
+            .add_event::<LakituWarning>()
+            .add_systems(
+                OnEnter(AppState::SpawningScene),
+                (fill_ragdoll_pool, reset_pursuer_scheduler, spawn_lakitu),
+            )
+            .add_systems(Update, (follow_cheese_with_lakitu, animate_lakitu).chain())
+            .add_systems(
+                Update,
+                (
+                    chase_cheese,
+                    drive_ragdolls,
+                    (tick_grab_cooldowns, grab_cheese, struggle_free).chain(),
+                    (recover_from_knockdowns, knock_down_people).chain(),
+                    spawn_ragdolls,
+                    loop_ragdolls,
+                    update_physics_lod,
+                    despawn_infinites,
+                )
+                    .run_if(in_state(AppState::Racing)),
             )
-                .run_if(in_state(AppState::Racing)),
-        );
+            .add_systems(OnExit(AppState::Racing), release_grip)
+            .add_systems(PostProcessCollisions, ignore_own_ragdoll_contacts);
     }
 }
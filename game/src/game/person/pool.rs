@@ -0,0 +1,249 @@
+use rand::Rng;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::GameLayer;
+
+use super::{
+    ActiveRagdoll, GrabCooldown, KnockedDown, Person, PersonArchetype, PhysicsLod, RagdollLimbs,
+    Skeleton, SkeletonAssets, SkeletonHandles,
+};
+
+// how many ragdolls to keep active, unless the `PursuerConfig` says otherwise
+#[cfg(target_arch = "wasm32")]
+pub const MAX_JUGGLE_COUNT: usize = 40;
+#[cfg(not(target_arch = "wasm32"))]
+pub const MAX_JUGGLE_COUNT: usize = 100;
+
+// Ragdoll meshes and colliders, built once per archetype and body size from the skeleton and
+// shared by every ragdoll of that archetype and size, and materials, built once per colour
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct RagdollAssets {
+    materials: HashMap<[u8; 4], Handle<StandardMaterial>>,
+    skeletons: HashMap<(PersonArchetype, (u32, u32)), SkeletonAssets>,
+}
+
+impl RagdollAssets {
+    fn material(
+        &mut self,
+        color: Color,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        self.materials
+            .entry(color.as_rgba_u8())
+            .or_insert_with(|| materials.add(color.into()))
+            .clone()
+    }
+
+    // the skeleton the person's ragdoll is built from
+    fn skeleton(&mut self, person: &Person) -> &mut SkeletonAssets {
+        self.skeletons
+            .entry((person.archetype(), person.size_bucket()))
+            .or_insert_with(|| SkeletonAssets::new(Skeleton::for_person(person)))
+    }
+
+    // the person's skeleton and the handles it is drawn with
+    pub fn skeleton_handles(
+        &mut self,
+        person: &Person,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> (&Skeleton, SkeletonHandles) {
+        let appearance = person.appearance();
+        let skin = self.material(appearance.skin, materials);
+        let shirt = self.material(appearance.shirt, materials);
+        let trousers = self.material(appearance.trousers, materials);
+        let hat_material = appearance
+            .hat
+            .map(|hat| (hat, self.material(hat.color(), materials)));
+        let assets = self.skeleton(person);
+        let (torso, bones) = assets
+            .meshes
+            .get_or_insert_with(|| assets.skeleton.meshes(meshes))
+            .clone();
+        let hat = hat_material.and_then(|(hat, material)| {
+            let head_radius = assets.skeleton.hat_radius()?;
+            let mesh = assets
+                .hats
+                .entry(hat)
+                .or_insert_with(|| meshes.add(hat.mesh(head_radius)))
+                .clone();
+            Some((mesh, material))
+        });
+        (
+            &assets.skeleton,
+            SkeletonHandles {
+                torso,
+                bones,
+                skin,
+                shirt,
+                trousers,
+                hat,
+            },
+        )
+    }
+
+    pub fn torso_collider(&mut self, person: &Person) -> (Collider, f32) {
+        self.skeleton(person).torso_collider.clone()
+    }
+
+    pub fn merged_collider(&mut self, person: &Person) -> (Collider, f32) {
+        self.skeleton(person).merged_collider.clone()
+    }
+
+    pub fn bucket_count(&self) -> usize {
+        self.skeletons.len()
+    }
+
+    pub fn material_count(&self) -> usize {
+        self.materials.len()
+    }
+}
+
+impl Person {
+    // people within a tenth of a unit of each other share meshes
+    fn size_bucket(&self) -> (u32, u32) {
+        (
+            (self.size * 10.).round() as u32,
+            (self.girth * 10.).round() as u32,
+        )
+    }
+}
+
+// A ragdoll that is out of the race, waiting in the `RagdollPool`.
+// Its colliders are sized for the person it was built for, so it keeps them
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct PooledRagdoll(pub Person);
+
+// Ragdolls that are built up front and recycled, rather than spawned and despawned mid-race
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct RagdollPool {
+    idle: Vec<(Entity, Person)>,
+}
+
+impl RagdollPool {
+    // parked ragdolls are frozen well out of sight below the course
+    pub const PARKING_SPOT: Vec3 = Vec3::new(0., -1000., 0.);
+    const PARKING_GAP: f32 = 5.;
+
+    pub fn idle_count(&self) -> usize {
+        self.idle.len()
+    }
+
+    // forgets the idle ragdolls, which are despawned with the rest of the scene
+    pub fn clear(&mut self) {
+        self.idle.clear();
+    }
+
+    // picks a random idle ragdoll, so that the one that was just parked isn't dropped straight back in
+    pub fn take(&mut self, rng: &mut impl Rng) -> Option<Entity> {
+        self.take_where(rng, |_| true)
+    }
+
+    // picks a random idle ragdoll built for a person that matches `filter`
+    pub fn take_where(
+        &mut self,
+        rng: &mut impl Rng,
+        filter: impl Fn(&Person) -> bool,
+    ) -> Option<Entity> {
+        let matches = self
+            .idle
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, person))| filter(person))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            None
+        } else {
+            let index = matches[rng.gen_range(0..matches.len())];
+            Some(self.idle.swap_remove(index).0)
+        }
+    }
+
+    // builds a ragdoll straight into the pool
+    pub fn build(
+        &mut self,
+        person: Person,
+        assets: &mut RagdollAssets,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        let (body, limbs) =
+            person.build_with_assets(Vec3::ZERO, Vec3::ZERO, assets, commands, meshes, materials);
+        self.park(body, person, &limbs, assets, commands);
+        body
+    }
+
+    // takes a ragdoll out of the race: it stops being a `Person`, and is hidden and frozen
+    pub fn park(
+        &mut self,
+        body: Entity,
+        person: Person,
+        limbs: &RagdollLimbs,
+        assets: &mut RagdollAssets,
+        commands: &mut Commands,
+    ) {
+        let spot = Self::PARKING_SPOT + Vec3::X * self.idle.len() as f32 * Self::PARKING_GAP;
+        let (torso_collider, torso_density) = assets.torso_collider(&person);
+        commands
+            .entity(body)
+            .remove::<(Person, GrabCooldown, KnockedDown)>()
+            .insert((
+                PooledRagdoll(person),
+                ActiveRagdoll::default(),
+                Visibility::Hidden,
+                RigidBody::Static,
+                Transform::from_translation(spot),
+                LinearVelocity::ZERO,
+                AngularVelocity::ZERO,
+                // back to a whole ragdoll, however it was simplified in the race
+                (
+                    PhysicsLod::Full,
+                    torso_collider,
+                    ColliderDensity(torso_density),
+                    GameLayer::ragdoll_body(true),
+                ),
+            ));
+        for limb in limbs.all() {
+            commands.entity(limb).insert((
+                RigidBody::Static,
+                GameLayer::ragdoll_limb(true),
+                LinearVelocity::ZERO,
+                AngularVelocity::ZERO,
+            ));
+        }
+        self.idle.push((body, person));
+    }
+
+    // puts a parked ragdoll back in the race, upright at `origin`
+    pub fn activate(
+        body: Entity,
+        person: Person,
+        limbs: &RagdollLimbs,
+        origin: Vec3,
+        velocity: Vec3,
+        commands: &mut Commands,
+    ) {
+        commands.entity(body).remove::<PooledRagdoll>().insert((
+            person,
+            Visibility::Inherited,
+            RigidBody::Dynamic,
+            Transform::from_translation(origin),
+            LinearVelocity(velocity),
+            AngularVelocity::ZERO,
+        ));
+        for limb in limbs.all() {
+            commands.entity(limb).insert((
+                RigidBody::Dynamic,
+                LinearVelocity(velocity),
+                AngularVelocity::ZERO,
+            ));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use rand::Rng;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::Difficulty;
+
+use super::{Person, MAX_JUGGLE_COUNT};
+
+// How many pursuers the lakitu keeps in the race, and where it drops them
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct PursuerConfig {
+    // the most ragdolls in the race at once
+    pub max_count: usize,
+    // past this many, ragdolls are dropped one at a time instead of in bursts
+    pub near_max_count: usize,
+    // how many times longer a single drop waits than a burst
+    pub near_max_slowdown: u32,
+    // where the lakitu's basket floats, relative to the cheese
+    pub lakitu_offset: Vec3,
+    // the average gap between ragdolls dropped side by side
+    pub gap: f32,
+    // how far below the lakitu's basket ragdolls are let go
+    pub drop_height: f32,
+    // dropped ragdolls start with this much of the cheese's velocity
+    pub release_speed: f32,
+    // ragdolls this far above or below the cheese are put back in the pool
+    pub recycle_distance: f32,
+    // giants are rare, so only a few are built
+    pub giant_count: usize,
+    pub patterns: Vec<PatternSchedule>,
+}
+
+impl Default for PursuerConfig {
+    fn default() -> Self {
+        Self {
+            max_count: MAX_JUGGLE_COUNT,
+            #[cfg(target_arch = "wasm32")]
+            near_max_count: 30,
+            #[cfg(not(target_arch = "wasm32"))]
+            near_max_count: 60,
+            near_max_slowdown: 2,
+            // add random x later
+            lakitu_offset: Vec3::new(0., 50., -40.),
+            gap: 8.,
+            drop_height: 4.,
+            release_speed: 0.8,
+            recycle_distance: 300.,
+            giant_count: 2,
+            patterns: vec![
+                PatternSchedule::new(SpawnPattern::Line, 0., 3.),
+                PatternSchedule::new(SpawnPattern::VFormation, 200., 2.),
+                PatternSchedule::new(SpawnPattern::Rain, 400., 1.5),
+                PatternSchedule::new(SpawnPattern::Pincer, 800., 1.5),
+                PatternSchedule::new(SpawnPattern::Giant, 1200., 0.5),
+            ],
+        }
+    }
+}
+
+impl PursuerConfig {
+    pub fn lakitu_point(&self, cheese_translation: Vec3) -> Vec3 {
+        cheese_translation + self.lakitu_offset
+    }
+
+    pub fn drop_point(&self, lakitu_translation: Vec3) -> Vec3 {
+        lakitu_translation - Vec3::Y * self.drop_height
+    }
+}
+
+// Which ragdolls from the pool a drop can use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PursuerSize {
+    // anyone but a giant
+    Any,
+    Small,
+    Giant,
+}
+
+impl PursuerSize {
+    pub const SMALL_SIZE: f32 = 2.1;
+    pub const GIANT_SIZE: f32 = 6.;
+    pub const GIANT_GIRTH: f32 = 4.;
+
+    pub fn fits(&self, person: &Person) -> bool {
+        match self {
+            PursuerSize::Any => person.size < Self::GIANT_SIZE,
+            PursuerSize::Small => person.size <= Self::SMALL_SIZE,
+            PursuerSize::Giant => person.size >= Self::GIANT_SIZE,
+        }
+    }
+}
+
+// One ragdoll to drop, relative to the lakitu's drop point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PursuerDrop {
+    pub offset: Vec3,
+    pub size: PursuerSize,
+}
+
+impl PursuerDrop {
+    pub fn new(offset: Vec3, size: PursuerSize) -> Self {
+        Self { offset, size }
+    }
+
+    // one ragdoll somewhere along the row that a line of `burst_size` would fill
+    pub fn single(burst_size: i32, config: &PursuerConfig, rng: &mut impl Rng) -> Self {
+        let burst_size = burst_size.max(0);
+        let index = rng.gen_range(-burst_size..=burst_size);
+        Self::new(Vec3::X * config.gap * index as f32, PursuerSize::Any)
+    }
+}
+
+// The formations the lakitu drops ragdolls in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpawnPattern {
+    // a row across the hill
+    Line,
+    // a row whose ends trail behind its middle
+    VFormation,
+    // two groups out wide, closing in from either side
+    Pincer,
+    // a single huge ragdoll
+    Giant,
+    // a scattered shower of small ragdolls
+    Rain,
+}
+
+impl SpawnPattern {
+    const PINCER_SPREAD: f32 = 30.;
+    const RAIN_SPREAD: f32 = 12.;
+
+    // `burst_size` is how many ragdolls drop either side of the middle of a line
+    pub fn drops(
+        &self,
+        burst_size: i32,
+        config: &PursuerConfig,
+        rng: &mut impl Rng,
+    ) -> Vec<PursuerDrop> {
+        let row = -burst_size..=burst_size;
+        match self {
+            SpawnPattern::Line => row
+                .map(|index| {
+                    PursuerDrop::new(Vec3::X * config.gap * index as f32, PursuerSize::Any)
+                })
+                .collect(),
+            SpawnPattern::VFormation => row
+                .map(|index| {
+                    let offset = Vec3::new(
+                        config.gap * index as f32,
+                        0.,
+                        -config.gap * 0.5 * index.abs() as f32,
+                    );
+                    PursuerDrop::new(offset, PursuerSize::Any)
+                })
+                .collect(),
+            SpawnPattern::Pincer => (0..=burst_size / 2)
+                .flat_map(|index| {
+                    let x = Self::PINCER_SPREAD + config.gap * index as f32;
+                    let z = config.gap * index as f32;
+                    [
+                        PursuerDrop::new(Vec3::new(-x, 0., z), PursuerSize::Any),
+                        PursuerDrop::new(Vec3::new(x, 0., z), PursuerSize::Any),
+                    ]
+                })
+                .collect(),
+            SpawnPattern::Giant => vec![PursuerDrop::new(Vec3::ZERO, PursuerSize::Giant)],
+            SpawnPattern::Rain => (0..(burst_size * 2 + 1) * 2)
+                .map(|_| {
+                    let width = config.gap * burst_size as f32;
+                    let offset = Vec3::new(
+                        rng.gen_range(-width..=width),
+                        rng.gen_range(0. ..=10.),
+                        rng.gen_range(-Self::RAIN_SPREAD..=Self::RAIN_SPREAD),
+                    );
+                    PursuerDrop::new(offset, PursuerSize::Small)
+                })
+                .collect(),
+        }
+    }
+}
+
+// When a pattern starts being picked, and how often
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PatternSchedule {
+    pub pattern: SpawnPattern,
+    // how far down the hill the pattern unlocks on normal difficulty
+    pub from_distance: f32,
+    pub weight: f32,
+}
+
+impl PatternSchedule {
+    pub fn new(pattern: SpawnPattern, from_distance: f32, weight: f32) -> Self {
+        Self {
+            pattern,
+            from_distance,
+            weight,
+        }
+    }
+}
+
+// Picks the pattern for each burst, and remembers when the last one was dropped
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct PursuerScheduler {
+    pub last_pattern: Option<SpawnPattern>,
+    // the app's elapsed time at the last drop
+    pub last_drop: Duration,
+}
+
+impl PursuerScheduler {
+    // the pattern that was just dropped is this much less likely to come straight back
+    const REPEAT_WEIGHT: f32 = 0.5;
+
+    pub fn pick(
+        &mut self,
+        config: &PursuerConfig,
+        distance: f32,
+        difficulty: Option<Difficulty>,
+        rng: &mut impl Rng,
+    ) -> SpawnPattern {
+        // harder difficulties unlock the patterns sooner
+        let unlock_scale = match difficulty {
+            Some(Difficulty::Easy) => 1.5,
+            Some(Difficulty::Hard) => 0.5,
+            Some(Difficulty::Normal) | None => 1.,
+        };
+        let weights = config
+            .patterns
+            .iter()
+            .filter(|schedule| schedule.from_distance * unlock_scale <= distance)
+            .map(|schedule| {
+                let weight = if self.last_pattern == Some(schedule.pattern) {
+                    schedule.weight * Self::REPEAT_WEIGHT
+                } else {
+                    schedule.weight
+                };
+                (schedule.pattern, weight.max(0.))
+            })
+            .collect::<Vec<_>>();
+        let total = weights.iter().map(|(_, weight)| weight).sum::<f32>();
+        let mut roll = rng.gen_range(0. ..=total);
+        let pattern = weights
+            .iter()
+            .find(|(_, weight)| {
+                roll -= weight;
+                roll <= 0.
+            })
+            .map(|(pattern, _)| *pattern)
+            .unwrap_or(SpawnPattern::Line);
+        self.last_pattern = Some(pattern);
+        pattern
+    }
+}
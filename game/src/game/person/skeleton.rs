@@ -0,0 +1,443 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::GameLayer;
+
+use super::{
+    ragdoll::Hand, ActiveRagdoll, DrivenLimb, Hat, LimbDrive, Person, PersonArchetype, PhysicsLod,
+    RagdollLimbs,
+};
+
+// The shape of a body part, which its collider and mesh are built from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoneShape {
+    Ball { radius: f32 },
+    // along the bone's Y axis
+    Capsule { length: f32, radius: f32 },
+    Box { size: Vec3 },
+}
+
+impl BoneShape {
+    pub fn collider(&self) -> Collider {
+        match *self {
+            BoneShape::Ball { radius } => Collider::ball(radius),
+            BoneShape::Capsule { length, radius } => Collider::capsule(length, radius),
+            BoneShape::Box { size } => Collider::cuboid(size.x, size.y, size.z),
+        }
+    }
+
+    pub fn mesh(&self) -> Mesh {
+        match *self {
+            BoneShape::Ball { radius } => shape::UVSphere {
+                radius,
+                ..Default::default()
+            }
+            .into(),
+            BoneShape::Capsule { length, radius } => shape::Capsule {
+                radius,
+                depth: length,
+                ..Default::default()
+            }
+            .into(),
+            BoneShape::Box { size } => shape::Box::new(size.x, size.y, size.z).into(),
+        }
+    }
+
+    pub fn volume(&self) -> f32 {
+        let ball = |radius: f32| 4. / 3. * std::f32::consts::PI * radius.powi(3);
+        match *self {
+            BoneShape::Ball { radius } => ball(radius),
+            BoneShape::Capsule { length, radius } => {
+                std::f32::consts::PI * radius.powi(2) * length + ball(radius)
+            }
+            BoneShape::Box { size } => size.x * size.y * size.z,
+        }
+    }
+
+    // the radius of a ball around the middle of the shape that holds all of it
+    pub fn reach(&self) -> f32 {
+        match *self {
+            BoneShape::Ball { radius } => radius,
+            BoneShape::Capsule { length, radius } => length / 2. + radius,
+            BoneShape::Box { size } => size.length() / 2.,
+        }
+    }
+}
+
+// Which of the person's colours a body part is drawn in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BonePaint {
+    Skin,
+    Shirt,
+    Trousers,
+}
+
+// One body part hanging off the torso
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bone {
+    pub name: &'static str,
+    pub shape: BoneShape,
+    pub paint: BonePaint,
+    // where the bone sits when the ragdoll is built, relative to the torso
+    pub transform: Transform,
+    pub density: f32,
+    // how the muscles swing the bone, if at all
+    pub drive: Option<LimbDrive>,
+    // whether the bone can take hold of the cheese
+    pub grabs: bool,
+    // whether the person's hat sits on the bone, which should be a ball
+    pub wears_hat: bool,
+}
+
+impl Bone {
+    pub fn new(name: &'static str, shape: BoneShape, paint: BonePaint, density: f32) -> Self {
+        Self {
+            name,
+            shape,
+            paint,
+            transform: Transform::default(),
+            density,
+            drive: None,
+            grabs: false,
+            wears_hat: false,
+        }
+    }
+
+    pub fn at(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn driven(mut self, drive: LimbDrive) -> Self {
+        self.drive = Some(drive);
+        self
+    }
+
+    pub fn grabbing(mut self) -> Self {
+        self.grabs = true;
+        self
+    }
+
+    pub fn wearing_hat(mut self) -> Self {
+        self.wears_hat = true;
+        self
+    }
+}
+
+// How a joint lets its bone move
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointKind {
+    // swings up to `limit` radians either way about `swing_axis`
+    Ball {
+        swing_axis: Vec3,
+        twist_axis: Vec3,
+        limit: f32,
+    },
+    // welded in place
+    Fixed,
+}
+
+// Ties a bone to the torso or to another bone
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoneJoint {
+    pub name: &'static str,
+    // the index of the parent bone, or `None` for the torso
+    pub parent: Option<usize>,
+    pub child: usize,
+    // where the joint sits on the parent and on the child, in each one's frame
+    pub parent_anchor: Vec3,
+    pub child_anchor: Vec3,
+    pub kind: JointKind,
+}
+
+impl BoneJoint {
+    const COMPLIANCE: f32 = 1e-7;
+
+    pub fn ball(
+        name: &'static str,
+        parent: Option<usize>,
+        child: usize,
+        anchors: (Vec3, Vec3),
+        limit: f32,
+    ) -> Self {
+        Self {
+            name,
+            parent,
+            child,
+            parent_anchor: anchors.0,
+            child_anchor: anchors.1,
+            kind: JointKind::Ball {
+                swing_axis: Vec3::X,
+                twist_axis: Vec3::Y,
+                limit,
+            },
+        }
+    }
+
+    pub fn fixed(
+        name: &'static str,
+        parent: Option<usize>,
+        child: usize,
+        anchors: (Vec3, Vec3),
+    ) -> Self {
+        Self {
+            name,
+            parent,
+            child,
+            parent_anchor: anchors.0,
+            child_anchor: anchors.1,
+            kind: JointKind::Fixed,
+        }
+    }
+
+    pub fn with_axes(mut self, swing_axis: Vec3, twist_axis: Vec3) -> Self {
+        if let JointKind::Ball { limit, .. } = self.kind {
+            self.kind = JointKind::Ball {
+                swing_axis,
+                twist_axis,
+                limit,
+            };
+        }
+        self
+    }
+
+    fn spawn(&self, body: Entity, bones: &[Entity], commands: &mut Commands) -> Entity {
+        let parent = self.parent.map_or(body, |index| bones[index]);
+        let child = bones[self.child];
+        let name = Name::new(self.name);
+        match self.kind {
+            JointKind::Ball {
+                swing_axis,
+                twist_axis,
+                limit,
+            } => commands.spawn((
+                name,
+                SphericalJoint {
+                    swing_axis,
+                    twist_axis,
+                    local_anchor1: self.parent_anchor,
+                    local_anchor2: self.child_anchor,
+                    compliance: Self::COMPLIANCE,
+                    swing_limit: Some(AngleLimit::new(-limit, limit)),
+                    ..SphericalJoint::new(parent, child)
+                },
+            )),
+            JointKind::Fixed => commands.spawn((
+                name,
+                FixedJoint::new(parent, child)
+                    .with_local_anchor_1(self.parent_anchor)
+                    .with_local_anchor_2(self.child_anchor)
+                    .with_compliance(Self::COMPLIANCE),
+            )),
+        }
+        .id()
+    }
+}
+
+// A ragdoll described as data: a torso, the bones hanging off it and the joints between them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Skeleton {
+    pub name: &'static str,
+    pub torso: BoneShape,
+    pub torso_paint: BonePaint,
+    pub torso_density: f32,
+    pub bones: Vec<Bone>,
+    pub joints: Vec<BoneJoint>,
+}
+
+// The meshes and materials a skeleton is drawn with
+#[derive(Clone, Debug)]
+pub struct SkeletonHandles {
+    pub torso: Handle<Mesh>,
+    // one for each bone, in the same order
+    pub bones: Vec<Handle<Mesh>>,
+    pub skin: Handle<StandardMaterial>,
+    pub shirt: Handle<StandardMaterial>,
+    pub trousers: Handle<StandardMaterial>,
+    pub hat: Option<(Handle<Mesh>, Handle<StandardMaterial>)>,
+}
+
+impl SkeletonHandles {
+    pub fn material(&self, paint: BonePaint) -> Handle<StandardMaterial> {
+        match paint {
+            BonePaint::Skin => self.skin.clone(),
+            BonePaint::Shirt => self.shirt.clone(),
+            BonePaint::Trousers => self.trousers.clone(),
+        }
+    }
+}
+
+// A skeleton and the meshes and colliders built from it, which every ragdoll of the same archetype
+// and size shares. The colliders come with the density that gives them the torso's mass
+#[derive(Clone, Debug)]
+pub struct SkeletonAssets {
+    pub skeleton: Skeleton,
+    pub torso_collider: (Collider, f32),
+    pub merged_collider: (Collider, f32),
+    // the torso's mesh, then each bone's, built the first time the skeleton is drawn
+    pub meshes: Option<(Handle<Mesh>, Vec<Handle<Mesh>>)>,
+    pub hats: HashMap<Hat, Handle<Mesh>>,
+}
+
+impl SkeletonAssets {
+    pub fn new(skeleton: Skeleton) -> Self {
+        Self {
+            torso_collider: (skeleton.torso.collider(), skeleton.torso_density),
+            merged_collider: skeleton.merged_collider(),
+            skeleton,
+            meshes: None,
+            hats: HashMap::default(),
+        }
+    }
+}
+
+impl Skeleton {
+    // the skeleton this person's ragdoll is built from
+    pub fn for_person(person: &Person) -> Self {
+        match person.archetype() {
+            PersonArchetype::Dog => Self::dog(person),
+            PersonArchetype::Regular
+            | PersonArchetype::Sprinter
+            | PersonArchetype::Heavy
+            | PersonArchetype::Child => Self::human(person),
+        }
+    }
+
+    // the radius of the head that hats sit on, if the skeleton wears them
+    pub fn hat_radius(&self) -> Option<f32> {
+        self.bones
+            .iter()
+            .find(|bone| bone.wears_hat)
+            .map(|bone| bone.shape.reach())
+    }
+
+    // the torso's mesh, then each bone's
+    pub fn meshes(&self, meshes: &mut Assets<Mesh>) -> (Handle<Mesh>, Vec<Handle<Mesh>>) {
+        (
+            meshes.add(self.torso.mesh()),
+            self.bones
+                .iter()
+                .map(|bone| meshes.add(bone.shape.mesh()))
+                .collect(),
+        )
+    }
+
+    // a single box around every bone, for when the ragdoll is merged into one body, and a density
+    // that keeps the torso's mass
+    pub fn merged_collider(&self) -> (Collider, f32) {
+        let torso_reach = self.torso.reach();
+        let (min, max) = self.bones.iter().fold(
+            (Vec3::splat(-torso_reach), Vec3::splat(torso_reach)),
+            |(min, max), bone| {
+                let reach = bone.shape.reach();
+                (
+                    min.min(bone.transform.translation - reach),
+                    max.max(bone.transform.translation + reach),
+                )
+            },
+        );
+        let size = max - min;
+        let merged = BoneShape::Box { size };
+        (
+            Collider::compound(vec![((min + max) / 2., Quat::IDENTITY, merged.collider())]),
+            self.torso_density * self.torso.volume() / merged.volume(),
+        )
+    }
+
+    pub fn build(
+        &self,
+        person: Person,
+        origin: Vec3,
+        initial_velocity: Vec3,
+        handles: &SkeletonHandles,
+        commands: &mut Commands,
+    ) -> (Entity, RagdollLimbs) {
+        let bones = self
+            .bones
+            .iter()
+            .zip(handles.bones.iter())
+            .map(|(bone, mesh)| {
+                let mut entity = commands.spawn((
+                    RigidBody::Dynamic,
+                    Name::new(bone.name),
+                    bone.shape.collider(),
+                    ColliderDensity(bone.density),
+                    GameLayer::ragdoll_limb(true),
+                    PbrBundle {
+                        mesh: mesh.clone(),
+                        material: handles.material(bone.paint),
+                        transform: bone.transform,
+                        ..Default::default()
+                    },
+                ));
+                if bone.grabs {
+                    entity.insert((Hand, CollidingEntities::default()));
+                }
+                if let (true, Some(hat), Some((mesh, material))) =
+                    (bone.wears_hat, person.appearance().hat, &handles.hat)
+                {
+                    entity.with_children(|parent| {
+                        parent.spawn((
+                            Name::new("Hat"),
+                            PbrBundle {
+                                mesh: mesh.clone(),
+                                material: material.clone(),
+                                transform: Transform::from_translation(
+                                    hat.offset(bone.shape.reach()),
+                                ),
+                                ..Default::default()
+                            },
+                        ));
+                    });
+                }
+                entity.id()
+            })
+            .collect::<Vec<_>>();
+
+        let limbs = RagdollLimbs {
+            parts: bones.clone(),
+            driven: self
+                .bones
+                .iter()
+                .zip(bones.iter())
+                .filter_map(|(bone, entity)| {
+                    bone.drive.map(|drive| DrivenLimb {
+                        entity: *entity,
+                        drive,
+                    })
+                })
+                .collect(),
+        };
+        let body = commands
+            .spawn((
+                person,
+                Name::new(self.name),
+                limbs.clone(),
+                ActiveRagdoll::default(),
+                RigidBody::Dynamic,
+                self.torso.collider(),
+                GameLayer::ragdoll_body(true),
+                PhysicsLod::Full,
+                ColliderDensity(self.torso_density),
+                GravityScale(1.1),
+                LinearVelocity(initial_velocity),
+                PbrBundle {
+                    mesh: handles.torso.clone(),
+                    material: handles.material(self.torso_paint),
+                    transform: Transform::from_translation(origin),
+                    ..Default::default()
+                },
+            ))
+            .push_children(&bones)
+            .id();
+
+        let joints = self
+            .joints
+            .iter()
+            .map(|joint| joint.spawn(body, &bones, commands))
+            .collect::<Vec<_>>();
+        commands.entity(body).push_children(&joints);
+
+        (body, limbs)
+    }
+}
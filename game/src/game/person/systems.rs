@@ -1,44 +1,517 @@
-use rand::Rng;
 use std::time::Duration;
 
 use bevy::{prelude::*, utils::HashSet};
 
 use bevy_xpbd_3d::prelude::*;
 
-use crate::{AppState, Cheese, Person};
+use crate::{
+    pd_angular_impulse, world_inertia, ActivePowerUps, ActiveRagdoll, AppState, Cheese, CheeseGrip,
+    DifficultyCurve, GameOverReason, GrabCooldown, Grabber, KnockedDown, Lakitu, LakituBurner,
+    LakituModel, LakituWarning, LimbMotion, Person, PersonAppearance, PersonKnockedDown,
+    PhysicsLod, PhysicsLodSettings, PooledRagdoll, PowerUpKind, PreviousVelocity, PursuerConfig,
+    PursuerDrop, PursuerScheduler, RagdollAssets, RagdollLimbs, RagdollPool, RagdollPose,
+    StruggleSide, RACE_START_Z,
+};
+
+use super::ragdoll::Hand;
 
 // systems
-const CHEESE_PULL_STRENGTH: f32 = 1.2e5;
+// knocked down people go limp, so they don't chase
 pub(crate) fn chase_cheese(
-    mut arm_query: Query<(&Transform, &mut ExternalImpulse), With<Person>>,
+    mut arm_query: Query<(&Person, &Transform, &mut ExternalImpulse), Without<KnockedDown>>,
     cheese_query: Query<&Transform, With<Cheese>>,
+    difficulty: Res<DifficultyCurve>,
+    power_ups: Res<ActivePowerUps>,
 ) {
     let Ok(cheese_transform) = cheese_query.get_single() else {
         return;
     };
-    arm_query.par_iter_mut().for_each(|(transform, mut force)| {
-        // each tick arms receive a magnetic impulse towards the cheese
-        let delta = cheese_transform.translation - transform.translation;
-        // N.B. this overwrites
-        force.set_impulse(CHEESE_PULL_STRENGTH * delta / delta.length_squared());
-    });
+    let pull_strength = difficulty
+        .sample(cheese_transform.translation.z)
+        .pull_strength;
+    // the repel power-up turns the pull into a push
+    let pull_strength = if power_ups.is_active(PowerUpKind::Repel) {
+        -pull_strength
+    } else {
+        pull_strength
+    };
+    arm_query
+        .par_iter_mut()
+        .for_each(|(person, transform, mut force)| {
+            // each tick arms receive a magnetic impulse towards the cheese
+            let delta = cheese_transform.translation - transform.translation;
+            let pull_strength = pull_strength * person.archetype().pull_scale();
+            // N.B. this overwrites
+            force.set_impulse(pull_strength * delta / delta.length_squared());
+        });
+}
+
+// the "muscles": PD-controlled torques that hold the torso up, swing the legs in a stride and
+// reach the arms for the cheese. the limbs are driven relative to the torso, like joint motors
+#[allow(clippy::type_complexity)]
+pub(crate) fn drive_ragdolls(
+    mut torso_query: Query<
+        (
+            &Person,
+            &RagdollLimbs,
+            &mut ActiveRagdoll,
+            &Position,
+            &Rotation,
+            &AngularVelocity,
+            &Inertia,
+            &mut ExternalAngularImpulse,
+            &PhysicsLod,
+            Has<KnockedDown>,
+        ),
+        Without<Cheese>,
+    >,
+    mut limb_query: Query<
+        (
+            &Position,
+            &Rotation,
+            &AngularVelocity,
+            &Inertia,
+            &mut ExternalAngularImpulse,
+        ),
+        (Without<Person>, Without<Cheese>),
+    >,
+    cheese_query: Query<&Transform, With<Cheese>>,
+    time: Res<Time>,
+) {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
+        return;
+    };
+    let cheese = cheese_transform.translation;
+    let delta = time.delta_seconds();
+    for (
+        person,
+        limbs,
+        mut ragdoll,
+        position,
+        rotation,
+        angular_velocity,
+        inertia,
+        mut angular_impulse,
+        lod,
+        knocked_down,
+    ) in torso_query.iter_mut()
+    {
+        let behaviour = person.behaviour();
+        let up = rotation.0 * Vec3::Y;
+        ragdoll.update_pose(up.dot(Vec3::Y), behaviour, delta);
+        // knocked down people stay limp until they come round
+        if knocked_down {
+            ragdoll.set_pose(RagdollPose::Fallen);
+        }
+        if ragdoll.pose == RagdollPose::Fallen {
+            continue;
+        }
+
+        // lean into the run, facing the cheese
+        let to_cheese = (cheese - position.0) * Vec3::new(1., 0., 1.);
+        let heading = to_cheese.try_normalize().unwrap_or(Vec3::Z);
+        let lean = Quat::from_axis_angle(heading.cross(Vec3::Y), -behaviour.lean);
+        let facing = Quat::from_rotation_arc(Vec3::Z, heading);
+        let torso_target = lean * facing;
+        angular_impulse.apply_impulse(pd_angular_impulse(
+            rotation.0,
+            torso_target,
+            angular_velocity.0,
+            behaviour.balance_stiffness * ragdoll.balance_scale(),
+            behaviour.balance_damping,
+            world_inertia(inertia.0, rotation.0),
+            delta,
+        ));
+        // merged limbs are carried by the torso, so there is nothing to swing
+        if ragdoll.pose == RagdollPose::GettingUp || *lod == PhysicsLod::Merged {
+            continue;
+        }
+
+        let reaching = cheese.distance(position.0) <= behaviour.reach_distance;
+        // where the far end of each limb should point: legs stride about the hips, and arms
+        // swing along with them until the cheese is close enough to reach
+        let stride = |angle: f32| rotation.0 * Quat::from_rotation_x(angle) * Vec3::NEG_Y;
+        for limb in limbs.driven.iter() {
+            let Ok((limb_position, limb_rotation, limb_velocity, limb_inertia, mut limb_impulse)) =
+                limb_query.get_mut(limb.entity)
+            else {
+                continue;
+            };
+            let swing = (ragdoll.gait_phase + limb.drive.phase).sin();
+            let direction = match limb.drive.motion {
+                LimbMotion::Stride => stride(behaviour.stride * swing),
+                LimbMotion::Reach if reaching => (cheese - limb_position.0).normalize_or_zero(),
+                LimbMotion::Reach => stride(behaviour.arm_swing * swing),
+            };
+            let current = limb_rotation.0 * limb.drive.axis;
+            let target = Quat::from_rotation_arc(current, direction) * limb_rotation.0;
+            limb_impulse.apply_impulse(pd_angular_impulse(
+                limb_rotation.0,
+                target,
+                limb_velocity.0 - angular_velocity.0,
+                behaviour.limb_stiffness,
+                behaviour.limb_damping,
+                world_inertia(limb_inertia.0, limb_rotation.0),
+                delta,
+            ));
+        }
+    }
+}
+
+// simplifies the physics of ragdolls that are far from the cheese
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_physics_lod(
+    mut commands: Commands,
+    query: Query<(
+        Entity,
+        &Person,
+        &RagdollLimbs,
+        &Transform,
+        &LinearVelocity,
+        &PhysicsLod,
+    )>,
+    cheese_query: Query<&Transform, (With<Cheese>, Without<Person>)>,
+    settings: Res<PhysicsLodSettings>,
+    mut assets: ResMut<RagdollAssets>,
+) {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
+        return;
+    };
+    for (entity, person, limbs, transform, velocity, lod) in query.iter() {
+        let distance = transform.translation.distance(cheese_transform.translation);
+        let next = settings.lod(*lod, distance);
+        if next != *lod {
+            next.apply(
+                *lod,
+                entity,
+                person,
+                limbs,
+                velocity.0,
+                &mut assets,
+                &mut commands,
+            );
+        }
+    }
+}
+
+// limbs are held to the rest of their ragdoll by joints, so their contacts with it are dropped
+pub(crate) fn ignore_own_ragdoll_contacts(
+    mut collisions: ResMut<Collisions>,
+    ragdoll_query: Query<(), With<RagdollLimbs>>,
+    parent_query: Query<&Parent>,
+) {
+    // limbs are children of the torso, which holds the `RagdollLimbs`
+    let ragdoll = |entity: Entity| {
+        if ragdoll_query.contains(entity) {
+            Some(entity)
+        } else {
+            parent_query
+                .get(entity)
+                .ok()
+                .map(Parent::get)
+                .filter(|parent| ragdoll_query.contains(*parent))
+        }
+    };
+    collisions.retain(
+        |contacts| match (ragdoll(contacts.entity1), ragdoll(contacts.entity2)) {
+            (Some(ragdoll1), Some(ragdoll2)) => ragdoll1 != ragdoll2,
+            _ => true,
+        },
+    );
+}
+
+// a hand that touches the cheese takes hold of it with a joint, unless its person is limp or
+// has just been shaken off
+#[allow(clippy::type_complexity)]
+pub(crate) fn grab_cheese(
+    mut commands: Commands,
+    hand_query: Query<(Entity, &Parent, &Position, &CollidingEntities), With<Hand>>,
+    cheese_query: Query<(Entity, &Position, &Rotation), (With<Cheese>, Without<Hand>)>,
+    cooldown_query: Query<(), Or<(With<GrabCooldown>, With<KnockedDown>)>>,
+    mut grip: ResMut<CheeseGrip>,
+    power_ups: Res<ActivePowerUps>,
+) {
+    if power_ups.is_active(PowerUpKind::Shield) {
+        return;
+    }
+    let Ok((cheese, cheese_position, cheese_rotation)) = cheese_query.get_single() else {
+        return;
+    };
+    for (hand, parent, hand_position, colliding_entities) in hand_query.iter() {
+        let person = parent.get();
+        if !colliding_entities.0.contains(&cheese)
+            || grip.holds(hand)
+            || cooldown_query.contains(person)
+        {
+            continue;
+        }
+        // hold on at the point the hand touched, in the cheese's frame so that it turns with it
+        let anchor = cheese_rotation.0.inverse() * (hand_position.0 - cheese_position.0);
+        let joint = commands
+            .spawn((
+                Name::new("Grab Joint"),
+                SphericalJoint::new(hand, cheese)
+                    .with_local_anchor_2(anchor)
+                    .with_compliance(1e-5),
+            ))
+            .id();
+        info!("Grabbed the cheese!");
+        grip.add(Grabber {
+            hand,
+            person,
+            joint,
+        });
+    }
 }
 
-pub(crate) fn detect_grab(
-    hand_query: Query<&CollidingEntities, With<Person>>,
-    cheese_query: Query<&Cheese>,
+// mashing left and right shakes the grabbers off, as long as it happens before the grip timer
+// runs out
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn struggle_free(
+    mut commands: Commands,
+    inputs: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut grip: ResMut<CheeseGrip>,
+    mut person_query: Query<(&Person, &Position, &mut LinearVelocity)>,
+    cheese_query: Query<&Position, (With<Cheese>, Without<Person>)>,
+    power_ups: Res<ActivePowerUps>,
     mut state: ResMut<NextState<AppState>>,
 ) {
-    for colliding_entities in hand_query.iter() {
-        for entity in colliding_entities.0.iter() {
-            if cheese_query.contains(*entity) {
-                info!("Caught the cheese!!!!");
-                state.set(AppState::GameOver);
+    // people that were despawned have let go already
+    grip.grabbers.retain(|grabber| {
+        let exists = person_query.contains(grabber.person);
+        if !exists {
+            if let Some(entity_commands) = commands.get_entity(grabber.joint) {
+                entity_commands.despawn_recursive();
+            }
+        }
+        exists
+    });
+    if !grip.is_held() {
+        return;
+    }
+    let Ok(cheese_position) = cheese_query.get_single() else {
+        return;
+    };
+
+    if inputs.any_just_pressed([KeyCode::Left, KeyCode::A]) {
+        grip.struggle(StruggleSide::Left);
+    }
+    if inputs.any_just_pressed([KeyCode::Right, KeyCode::D]) {
+        grip.struggle(StruggleSide::Right);
+    }
+
+    // a shield slips out of any grip
+    if grip.escape_fraction() >= 1. || power_ups.is_active(PowerUpKind::Shield) {
+        info!("Escaped the grab!");
+        for grabber in grip.release() {
+            if let Some(entity_commands) = commands.get_entity(grabber.joint) {
+                entity_commands.despawn_recursive();
+            }
+            commands
+                .entity(grabber.person)
+                .insert(GrabCooldown::default());
+            if let Ok((_, position, mut velocity)) = person_query.get_mut(grabber.person) {
+                let away = (position.0 - cheese_position.0).normalize_or_zero() + Vec3::Y;
+                velocity.0 = away * CheeseGrip::ESCAPE_SPEED;
             }
         }
+        return;
+    }
+
+    if grip.timer.tick(time.delta()).finished() {
+        let Ok((person, _, _)) = person_query.get(grip.grabbers[0].person) else {
+            return;
+        };
+        info!("Caught the cheese!!!!");
+        commands.insert_resource(GameOverReason::Caught(*person));
+        state.set(AppState::GameOver);
+    }
+}
+
+pub(crate) fn tick_grab_cooldowns(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut GrabCooldown)>,
+    time: Res<Time>,
+) {
+    for (entity, mut cooldown) in query.iter_mut() {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<GrabCooldown>();
+        }
+    }
+}
+
+// the cheese bowls over anyone it hits hard enough, throwing them along the hit
+#[allow(clippy::type_complexity)]
+pub(crate) fn knock_down_people(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    cheese_query: Query<(Entity, &PreviousVelocity, &Mass), With<Cheese>>,
+    parent_query: Query<&Parent>,
+    mut person_query: Query<
+        (&mut LinearVelocity, &Mass, Has<KnockedDown>),
+        (With<Person>, Without<Cheese>),
+    >,
+    mut events: EventWriter<PersonKnockedDown>,
+) {
+    let Ok((cheese, cheese_velocity, cheese_mass)) = cheese_query.get_single() else {
+        return;
+    };
+    // a hit often touches several body parts at once, but only counts once
+    let mut knocked_down = HashSet::new();
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let other = if *entity1 == cheese {
+            *entity2
+        } else if *entity2 == cheese {
+            *entity1
+        } else {
+            continue;
+        };
+        // limbs are children of the torso, which is the person
+        let person = if person_query.contains(other) {
+            other
+        } else {
+            parent_query.get(other).map_or(other, Parent::get)
+        };
+        let Ok((mut velocity, mass, already_down)) = person_query.get_mut(person) else {
+            continue;
+        };
+        if already_down || knocked_down.contains(&person) {
+            continue;
+        }
+        // the cheese's velocity from before the contact was solved
+        let relative_velocity = cheese_velocity.0 - velocity.0;
+        let speed_change =
+            KnockedDown::speed_change(relative_velocity.length(), cheese_mass.0, mass.0);
+        if speed_change < KnockedDown::MIN_SPEED_CHANGE {
+            continue;
+        }
+        let direction = relative_velocity.normalize_or_zero() + Vec3::Y * KnockedDown::LIFT;
+        velocity.0 += direction * speed_change;
+        knocked_down.insert(person);
+        commands
+            .entity(person)
+            .insert(KnockedDown::from_speed_change(speed_change));
+        events.send(PersonKnockedDown {
+            person,
+            impulse: speed_change * mass.0,
+        });
+    }
+}
+
+pub(crate) fn recover_from_knockdowns(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut KnockedDown)>,
+    time: Res<Time>,
+) {
+    for (entity, mut knocked_down) in query.iter_mut() {
+        if knocked_down.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<KnockedDown>();
+        }
+    }
+}
+
+// the grab joints aren't part of any ragdoll, so they are cleaned up when the race ends
+pub(crate) fn release_grip(mut commands: Commands, mut grip: ResMut<CheeseGrip>) {
+    for grabber in grip.release() {
+        if let Some(entity_commands) = commands.get_entity(grabber.joint) {
+            entity_commands.despawn_recursive();
+        }
+    }
+}
+
+// builds the race's ragdolls up front, so that none are spawned mid-race
+pub(crate) fn fill_ragdoll_pool(
+    mut commands: Commands,
+    config: Res<PursuerConfig>,
+    mut pool: ResMut<RagdollPool>,
+    mut assets: ResMut<RagdollAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    pool.clear();
+    let mut rng = rand::thread_rng();
+    let giants = (0..config.giant_count)
+        .map(|_| Person::giant().with_appearance(PersonAppearance::random(&mut rng)))
+        .collect::<Vec<_>>();
+    let people = (0..config.max_count)
+        .map(|_| Person::random(&mut rng))
+        .collect::<Vec<_>>();
+    for person in giants.into_iter().chain(people) {
+        pool.build(
+            person,
+            &mut assets,
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
+
+pub(crate) fn reset_pursuer_scheduler(mut scheduler: ResMut<PursuerScheduler>) {
+    *scheduler = PursuerScheduler::default();
+}
+
+pub(crate) fn spawn_lakitu(
+    mut commands: Commands,
+    config: Res<PursuerConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    Lakitu::spawn(
+        config.lakitu_point(Vec3::Z * RACE_START_Z),
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+    );
+}
+
+pub(crate) fn follow_cheese_with_lakitu(
+    mut lakitu_query: Query<&mut Transform, With<Lakitu>>,
+    cheese_query: Query<&Transform, (With<Cheese>, Without<Lakitu>)>,
+    config: Res<PursuerConfig>,
+) {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
+        return;
+    };
+    // if the cheese goes into oblivion, the lakitu stays where it was
+    if !cheese_transform.translation.is_finite() {
+        return;
+    }
+    for mut transform in lakitu_query.iter_mut() {
+        transform.translation = config.lakitu_point(cheese_transform.translation);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn animate_lakitu(
+    mut lakitu_query: Query<&mut Lakitu>,
+    mut model_query: Query<&mut Transform, With<LakituModel>>,
+    mut burner_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<LakituBurner>, Without<LakituModel>),
+    >,
+    time: Res<Time>,
+) {
+    let Ok(mut lakitu) = lakitu_query.get_single_mut() else {
+        return;
+    };
+    lakitu.age += time.delta_seconds();
+    for mut transform in model_query.iter_mut() {
+        *transform = lakitu.model_transform();
+    }
+    for (mut transform, mut visibility) in burner_query.iter_mut() {
+        *visibility = if lakitu.is_warning() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        transform.scale = Vec3::splat(lakitu.burner_scale());
     }
 }
 
+// broken ragdolls can't be put back together, so they are replaced in the pool with new ones
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub(crate) fn despawn_infinites(
     mut commands: Commands,
     query: Query<(
@@ -48,6 +521,11 @@ pub(crate) fn despawn_infinites(
         &AngularVelocity,
         Option<&Parent>,
     )>,
+    person_query: Query<&Person>,
+    mut pool: ResMut<RagdollPool>,
+    mut assets: ResMut<RagdollAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let entities_to_remove = query
         .iter()
@@ -68,126 +546,128 @@ pub(crate) fn despawn_infinites(
         })
         .collect::<HashSet<Entity>>();
     for entity in entities_to_remove {
+        if let Ok(person) = person_query.get(entity) {
+            pool.build(
+                *person,
+                &mut assets,
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+            );
+        }
         commands.entity(entity).despawn_recursive();
     }
 }
 
-// aka the "lakitu" system
-#[allow(clippy::type_complexity)]
+// ragdolls that the cheese has left far behind go back in the pool for the lakitu to drop again
 pub(crate) fn loop_ragdolls(
-    mut ragdoll_query: Query<
-        (&mut Transform, &mut LinearVelocity, &mut AngularVelocity),
-        With<Person>,
-    >,
-    cheese_query: Query<(&Transform, &LinearVelocity), (With<Cheese>, Without<Person>)>,
+    mut commands: Commands,
+    ragdoll_query: Query<(Entity, &Person, &RagdollLimbs, &Transform)>,
+    cheese_query: Query<&Transform, (With<Cheese>, Without<Person>)>,
+    config: Res<PursuerConfig>,
+    mut pool: ResMut<RagdollPool>,
+    mut assets: ResMut<RagdollAssets>,
 ) {
-    let Ok((cheese_transform, cheese_velocity)) = cheese_query.get_single() else {
+    let Ok(cheese_transform) = cheese_query.get_single() else {
         return;
     };
-
-    let mut num_to_loop = 0;
-    for (transform, _, _) in ragdoll_query.iter() {
-        if (cheese_transform.translation.y - transform.translation.y).abs() >= 300.
+    for (entity, person, limbs, transform) in ragdoll_query.iter() {
+        if (cheese_transform.translation.y - transform.translation.y).abs()
+            >= config.recycle_distance
             || transform.translation.is_nan()
         {
-            num_to_loop += 1;
-        }
-    }
-    let mut rng = rand::thread_rng();
-    let random_offset = rng.gen_range(-30..=30);
-    let mut num_looped = 0;
-    for (mut transform, mut linvel, mut angvel) in ragdoll_query.iter_mut() {
-        if (cheese_transform.translation.y - transform.translation.y).abs() >= 300.
-            || transform.translation.is_nan()
-        {
-            *transform = Transform::from_translation(
-                get_spawn_point(
-                    cheese_transform.translation,
-                    num_looped - num_to_loop / 2,
-                    random_offset as f32,
-                )
-                // add a vertical component to avoid collisions with spawn_ragdolls 
-                + Vec3::Y * 5.,
-            );
-            num_looped += 1;
-            *linvel = (cheese_velocity.0 * 0.8).into();
-            *angvel = AngularVelocity::ZERO;
+            pool.park(entity, *person, limbs, &mut assets, &mut commands);
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
+// aka the "lakitu" system: each drop is announced, and the ragdolls are held back in the pool
+// until the warning runs out and the lakitu lets them go
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub(crate) fn spawn_ragdolls(
     mut commands: Commands,
-    ragdoll_query: Query<(Entity, &Transform), With<Person>>,
+    ragdoll_query: Query<(), With<Person>>,
+    pooled_query: Query<(&PooledRagdoll, &RagdollLimbs)>,
     cheese_query: Query<(&Transform, &LinearVelocity), (With<Cheese>, Without<Person>)>,
+    mut lakitu_query: Query<(&mut Lakitu, &Transform), (Without<Cheese>, Without<Person>)>,
     time: Res<Time>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut last_spawned_time: Local<Duration>,
+    difficulty: Res<DifficultyCurve>,
+    config: Res<PursuerConfig>,
+    mut scheduler: ResMut<PursuerScheduler>,
+    mut pool: ResMut<RagdollPool>,
+    mut warnings: EventWriter<LakituWarning>,
 ) {
     let Ok((cheese_transform, cheese_velocity)) = cheese_query.get_single() else {
         return;
     };
+    let Ok((mut lakitu, lakitu_transform)) = lakitu_query.get_single_mut() else {
+        return;
+    };
+    if lakitu.is_warning() {
+        let Some(pending) = lakitu.tick(time.delta()) else {
+            return;
+        };
+        let drop_point = config.drop_point(lakitu_transform.translation);
+        for (entity, offset) in pending {
+            let Ok((pooled, limbs)) = pooled_query.get(entity) else {
+                continue;
+            };
+            RagdollPool::activate(
+                entity,
+                pooled.0,
+                limbs,
+                drop_point + offset,
+                cheese_velocity.0 * config.release_speed,
+                &mut commands,
+            );
+        }
+        return;
+    }
+
+    let distance = cheese_transform.translation.z;
+    let settings = difficulty.sample(distance);
 
-    // how many ragdolls to keep active
-    #[cfg(target_arch = "wasm32")]
-    const MAX_JUGGLE_COUNT: usize = 40;
-    #[cfg(not(target_arch = "wasm32"))]
-    const MAX_JUGGLE_COUNT: usize = 100;
-    #[cfg(target_arch = "wasm32")]
-    const NEAR_MAX_COUNT: usize = 30;
-    #[cfg(not(target_arch = "wasm32"))]
-    const NEAR_MAX_COUNT: usize = 60;
-
-    // use different spawn rates when near max and not
-    const LOW_COUNT_SPAWN_RATE: Duration = Duration::from_secs(2);
-    const HIGH_COUNT_SPAWN_RATE: Duration = Duration::from_secs(4);
-
-    let time_since_last_spawn = time.elapsed() - *last_spawned_time;
+    // use different spawn rates when near max and not. curves can be built from any data, so
+    // don't trust the interval to be positive
+    let low_count_spawn_rate = Duration::from_secs_f32(settings.spawn_interval.max(0.));
+    let high_count_spawn_rate = low_count_spawn_rate * config.near_max_slowdown;
+
+    let time_since_last_spawn = time.elapsed().saturating_sub(scheduler.last_drop);
     let num_ragdolls = ragdoll_query.iter().count();
 
     let mut rng = rand::thread_rng();
-    let mut spawn_ragdoll = |index: Option<i32>| {
-        let index = index.unwrap_or_else(|| rng.gen_range(0..8));
-        Person::new(
-            1.5 + rng.gen_range(1..=10) as f32 / 5.,
-            1.5 + rng.gen_range(1..=10) as f32 / 5.,
-        )
-        .spawn_ragdoll(
-            get_spawn_point(cheese_transform.translation, index, 0.) + Vec3::Y * 4.,
-            cheese_velocity.0 * 0.8,
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-        );
-        *last_spawned_time = time.elapsed();
-    };
-
-    if num_ragdolls >= MAX_JUGGLE_COUNT {
+    let drops = if num_ragdolls >= config.max_count {
         // do nothing
-    } else if num_ragdolls > NEAR_MAX_COUNT {
+        vec![]
+    } else if num_ragdolls > config.near_max_count {
         // spawn ragdolls slowly
-        if time_since_last_spawn > HIGH_COUNT_SPAWN_RATE {
-            spawn_ragdoll(None)
+        if time_since_last_spawn > high_count_spawn_rate {
+            vec![PursuerDrop::single(settings.burst_size, &config, &mut rng)]
+        } else {
+            vec![]
         }
-    } else {
+    } else if time_since_last_spawn > low_count_spawn_rate {
         // spawn bursts of ragdolls
-        if time_since_last_spawn > LOW_COUNT_SPAWN_RATE {
-            for index in -4..=4 {
-                spawn_ragdoll(Some(index));
-            }
-        }
-    }
-}
-
-// add random x later
-const LAKITU_OFFSET: Vec3 = Vec3::new(0., 50., -40.);
+        scheduler
+            .pick(&config, distance, difficulty.preset, &mut rng)
+            .drops(settings.burst_size, &config, &mut rng)
+    } else {
+        vec![]
+    };
 
-fn get_spawn_point(cheese_translation: Vec3, index: i32, additional_offset: f32) -> Vec3 {
-    const AVG_GAP: f32 = 8.;
-    cheese_translation
-        + LAKITU_OFFSET
-        + Vec3::X * AVG_GAP * index as f32
-        + Vec3::X * additional_offset
+    // an empty pool means every ragdoll of that size is already in the race
+    let pending = drops
+        .into_iter()
+        .filter_map(|drop| {
+            pool.take_where(&mut rng, |person| drop.size.fits(person))
+                .map(|entity| (entity, drop.offset))
+        })
+        .collect::<Vec<_>>();
+    if !pending.is_empty() {
+        lakitu.warn(pending);
+        warnings.send(LakituWarning {
+            translation: lakitu_transform.translation,
+        });
+        scheduler.last_drop = time.elapsed();
+    }
 }
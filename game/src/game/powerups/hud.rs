@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use super::{ActivePowerUps, PowerUpKind};
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct PowerUpHud;
+// One slot for each power-up with a lasting effect, hidden while it isn't active
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct PowerUpIcon(PowerUpKind);
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub(super) struct PowerUpTimerText(PowerUpKind);
+
+pub(super) fn spawn_power_up_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Power-up HUD"),
+            PowerUpHud,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(1.),
+                    top: Val::Percent(1.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|builder| {
+            for kind in PowerUpKind::ALL {
+                if kind.duration().is_none() {
+                    continue;
+                }
+                builder
+                    .spawn((
+                        Name::new(format!("{} Icon", kind.label())),
+                        PowerUpIcon(kind),
+                        NodeBundle {
+                            style: Style {
+                                display: Display::None,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(8.),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(32.),
+                                height: Val::Px(32.),
+                                ..Default::default()
+                            },
+                            background_color: kind.color().into(),
+                            ..Default::default()
+                        });
+                        builder.spawn((
+                            PowerUpTimerText(kind),
+                            TextBundle::from_section(
+                                kind.label(),
+                                TextStyle {
+                                    font_size: 32.,
+                                    ..Default::default()
+                                },
+                            ),
+                        ));
+                    });
+            }
+        });
+}
+
+pub(super) fn track_power_up_hud(
+    mut icon_query: Query<(&PowerUpIcon, &mut Style)>,
+    mut text_query: Query<(&PowerUpTimerText, &mut Text)>,
+    active: Res<ActivePowerUps>,
+) {
+    for (icon, mut style) in icon_query.iter_mut() {
+        let display = if active.is_active(icon.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    for (timer_text, mut text) in text_query.iter_mut() {
+        if let Some(remaining) = active.remaining(timer_text.0) {
+            text.sections[0].value = format!("{} {:.0}", timer_text.0.label(), remaining.ceil());
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use ::noise::NoiseFn;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{
+    despawn_all_recursive, AppState, GameLayer, Level, Obstacles, TerrainChunk, TerrainNoise,
+    Vertex,
+};
+
+use super::Chunk;
+
+mod hud;
+use hud::*;
+
+mod systems;
+use systems::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum PowerUpKind {
+    // pushes the cheese along the way it is already rolling
+    Boost,
+    // the ragdolls can't catch the cheese
+    Shield,
+    // the ragdolls are pushed away from the cheese instead of pulled towards it
+    Repel,
+    // slows down the whole race
+    SlowMotion,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Boost,
+        PowerUpKind::Shield,
+        PowerUpKind::Repel,
+        PowerUpKind::SlowMotion,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::Boost => "Boost",
+            PowerUpKind::Shield => "Shield",
+            PowerUpKind::Repel => "Repel",
+            PowerUpKind::SlowMotion => "Slow-mo",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::Boost => Color::ORANGE_RED,
+            PowerUpKind::Shield => Color::CYAN,
+            PowerUpKind::Repel => Color::FUCHSIA,
+            PowerUpKind::SlowMotion => Color::LIME_GREEN,
+        }
+    }
+
+    // how long the effect lasts in real seconds, or None if it happens all at once
+    pub fn duration(&self) -> Option<f32> {
+        match self {
+            PowerUpKind::Boost => None,
+            PowerUpKind::Shield => Some(8.),
+            PowerUpKind::Repel => Some(4.),
+            PowerUpKind::SlowMotion => Some(5.),
+        }
+    }
+
+    // how often the kind is picked relative to the others
+    fn weight(&self) -> u32 {
+        match self {
+            PowerUpKind::Boost => 4,
+            PowerUpKind::Shield => 2,
+            PowerUpKind::Repel => 2,
+            PowerUpKind::SlowMotion => 1,
+        }
+    }
+
+    fn choose(rng: &mut impl Rng) -> Self {
+        let total_weight: u32 = Self::ALL.iter().map(PowerUpKind::weight).sum();
+        let mut roll = rng.gen_range(0..total_weight);
+        for kind in Self::ALL {
+            if roll < kind.weight() {
+                return kind;
+            }
+            roll -= kind.weight();
+        }
+        PowerUpKind::Boost
+    }
+}
+
+// A pickup floating above the hill
+#[derive(Clone, Debug)]
+#[derive(Component, Reflect)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    // in global grid units, see `Chunk::to_global_coords`
+    pub vertex: Vertex,
+}
+
+impl PowerUp {
+    const RADIUS: f32 = 0.5;
+    // generous, so that the cheese doesn't have to hit it dead on
+    const PICKUP_RADIUS: f32 = 1.2;
+    const HOVER_HEIGHT: f32 = 1.2;
+
+    pub fn new(kind: PowerUpKind, vertex: Vertex) -> Self {
+        Self { kind, vertex }
+    }
+
+    pub fn spawn(
+        self,
+        level: &Level,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        let grid_translation = Vec3::new(
+            self.vertex.x as f32 * level.quad_size.x,
+            0.,
+            self.vertex.z as f32 * level.quad_size.y,
+        );
+        let surface = TerrainChunk::surface_position(
+            grid_translation,
+            level.chunk_size,
+            level.quad_size,
+            terrain_noise,
+        );
+        let color = self.kind.color();
+        commands
+            .spawn((
+                Name::new(format!("{} Power-up", self.kind.label())),
+                RigidBody::Static,
+                Sensor,
+                Collider::ball(Self::PICKUP_RADIUS),
+                GameLayer::sensor(),
+                PbrBundle {
+                    mesh: meshes.add(
+                        shape::Cube {
+                            size: Self::RADIUS * 2.,
+                        }
+                        .into(),
+                    ),
+                    material: materials.add(StandardMaterial {
+                        base_color: color,
+                        emissive: color,
+                        ..Default::default()
+                    }),
+                    transform: Transform::from_translation(surface + Vec3::Y * Self::HOVER_HEIGHT)
+                        .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_4)),
+                    ..Default::default()
+                },
+                self,
+            ))
+            .id()
+    }
+}
+
+// Tracks which power-ups have been spawned for each level chunk
+#[derive(Debug, Clone, Default)]
+#[derive(Component)]
+pub struct PowerUps {
+    pub chunk_entities: HashMap<Vertex, Vec<Entity>>,
+}
+
+impl PowerUps {
+    const MAX_PER_CHUNK: u32 = 2;
+
+    // power-ups share the obstacles' chunks, and are seeded from the race seed and the chunk so
+    // that the same race always has the same pickups
+    pub fn generate_for_chunk(chunk: Chunk, seed: u32) -> impl Iterator<Item = PowerUp> {
+        let mut rng = StdRng::seed_from_u64(
+            ((seed as u64) << 32)
+                ^ ((chunk.origin.x as u32 as u64) << 16)
+                ^ (chunk.origin.z as u32 as u64),
+        );
+        // leave the start of the race clear, like the obstacles do
+        let count = if chunk.origin.z <= 0 {
+            0
+        } else {
+            rng.gen_range(0..=Self::MAX_PER_CHUNK)
+        };
+        (0..count).map(move |_| {
+            let kind = PowerUpKind::choose(&mut rng);
+            let vertex = Vertex::new(
+                rng.gen_range(0..chunk.size.x),
+                rng.gen_range(0..chunk.size.z - 1),
+            );
+            PowerUp::new(kind, chunk.to_global_coords(vertex))
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        level: &Level,
+        seed: u32,
+        terrain_noise: &impl NoiseFn<f64, 2>,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        self.chunk_entities.retain(|vertex, entities| {
+            if level.chunks_in_play.contains(vertex) {
+                return true;
+            }
+            for entity in entities.iter() {
+                // collected power-ups are already gone
+                if let Some(entity_commands) = commands.get_entity(*entity) {
+                    entity_commands.despawn_recursive();
+                }
+            }
+            false
+        });
+
+        for origin in level.chunks_in_play.iter() {
+            if !self.chunk_entities.contains_key(origin) {
+                let chunk =
+                    Obstacles::chunk_for_level_origin(*origin, level.chunk_size, level.quad_size);
+                let chunk_entities = Self::generate_for_chunk(chunk, seed)
+                    .map(|power_up| {
+                        power_up.spawn(level, terrain_noise, commands, meshes, materials)
+                    })
+                    .collect();
+                self.chunk_entities.insert(*origin, chunk_entities);
+            }
+        }
+    }
+}
+
+// The power-ups whose effects are currently running, and how long each has left
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct ActivePowerUps {
+    pub timers: HashMap<PowerUpKind, Timer>,
+}
+
+impl ActivePowerUps {
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.timers.contains_key(&kind)
+    }
+
+    // seconds left on the effect, if it is active
+    pub fn remaining(&self, kind: PowerUpKind) -> Option<f32> {
+        self.timers.get(&kind).map(Timer::remaining_secs)
+    }
+
+    // starts the effect, or restarts it if it is already running
+    pub fn activate(&mut self, kind: PowerUpKind) {
+        if let Some(duration) = kind.duration() {
+            self.timers
+                .insert(kind, Timer::from_seconds(duration, TimerMode::Once));
+        }
+    }
+}
+
+pub struct PowerUpsPlugin;
+
+impl Plugin for PowerUpsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActivePowerUps>()
+            .add_systems(Update, attach_power_ups)
+            .add_systems(
+                Update,
+                update_power_ups.run_if(
+                    resource_exists::<TerrainNoise>().and_then(not(in_state(AppState::Editor))),
+                ),
+            )
+            .add_systems(OnEnter(AppState::SpawningScene), spawn_power_up_hud)
+            .add_systems(
+                Update,
+                (
+                    spin_power_ups,
+                    collect_power_ups,
+                    tick_power_ups,
+                    track_power_up_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Racing)),
+            )
+            .add_systems(
+                OnExit(AppState::Racing),
+                (
+                    apply_deferred,
+                    (clear_power_ups, despawn_all_recursive::<PowerUpHud>),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{Cheese, Level, RaceSeed, TerrainNoise};
+
+use super::{ActivePowerUps, PowerUp, PowerUpKind, PowerUps};
+
+// how much speed a boost adds along the cheese's direction of travel
+const BOOST_SPEED: f32 = 12.;
+// how fast the race runs while slow-motion is active
+const SLOW_MOTION_SPEED: f32 = 0.5;
+
+pub(super) fn attach_power_ups(mut commands: Commands, query: Query<Entity, Added<Level>>) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(PowerUps::default());
+    }
+}
+
+pub(super) fn update_power_ups(
+    mut commands: Commands,
+    mut power_ups_query: Query<(&mut PowerUps, &Level)>,
+    seed: Res<RaceSeed>,
+    terrain_noise: Res<TerrainNoise>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (mut power_ups, level) in power_ups_query.iter_mut() {
+        power_ups.update(
+            level,
+            seed.0,
+            &terrain_noise.get(),
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
+
+pub(super) fn spin_power_ups(mut query: Query<&mut Transform, With<PowerUp>>, time: Res<Time>) {
+    const ROTATION_SPEED: f32 = 2.;
+    for mut transform in query.iter_mut() {
+        transform.rotate_y(ROTATION_SPEED * time.delta_seconds());
+    }
+}
+
+pub(super) fn collect_power_ups(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionStarted>,
+    mut cheese_query: Query<(Entity, &mut LinearVelocity), With<Cheese>>,
+    power_up_query: Query<&PowerUp>,
+    mut active: ResMut<ActivePowerUps>,
+) {
+    let Ok((cheese, mut velocity)) = cheese_query.get_single_mut() else {
+        return;
+    };
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let other = if *entity1 == cheese {
+            *entity2
+        } else if *entity2 == cheese {
+            *entity1
+        } else {
+            continue;
+        };
+        let Ok(power_up) = power_up_query.get(other) else {
+            continue;
+        };
+        info!("Collected {}", power_up.kind.label());
+        if power_up.kind == PowerUpKind::Boost {
+            let direction = velocity.0.normalize_or_zero();
+            velocity.0 += direction * BOOST_SPEED;
+        }
+        active.activate(power_up.kind);
+        commands.entity(other).despawn_recursive();
+    }
+}
+
+// effects run on real time, so that slow-motion doesn't make itself last longer
+pub(super) fn tick_power_ups(
+    mut active: ResMut<ActivePowerUps>,
+    real_time: Res<Time<Real>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    active
+        .timers
+        .retain(|_, timer| !timer.tick(real_time.delta()).finished());
+    let speed = if active.is_active(PowerUpKind::SlowMotion) {
+        SLOW_MOTION_SPEED
+    } else {
+        1.
+    };
+    if virtual_time.relative_speed() != speed {
+        virtual_time.set_relative_speed(speed);
+    }
+}
+
+pub(super) fn clear_power_ups(
+    mut active: ResMut<ActivePowerUps>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    active.timers.clear();
+    virtual_time.set_relative_speed(1.);
+}
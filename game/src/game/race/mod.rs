@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::{AppState, Course};
+
+mod outcome;
+pub use outcome::*;
+
+mod stats;
+pub use stats::*;
+
+mod watchdog;
+pub use watchdog::*;
+
+// The seed that everything random about a race is derived from, so that a race can be replayed
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub struct RaceSeed(pub u32);
+
+// How long the current race has been running, which scripted obstacles are animated from
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub struct RaceClock(pub f32);
+
+// The rules a race is played by, picked on the main menu
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Resource)]
+pub enum GameMode {
+    // a stalled cheese ends the run
+    #[default]
+    Classic,
+    // a stalled cheese is wobbled loose and keeps going
+    Relaxed,
+    // the run goes on until the cheese is caught, and only flattened pursuers score
+    Bowling,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [GameMode::Classic, GameMode::Relaxed, GameMode::Bowling];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Relaxed => "Relaxed",
+            GameMode::Bowling => "Bowling",
+        }
+    }
+
+    // what the score is made of before any style points. the distance itself is already listed
+    // with the rest of the run's stats, so this names the points rather than the metres
+    pub fn score_label(&self) -> &'static str {
+        match self {
+            GameMode::Classic | GameMode::Relaxed => "Distance score",
+            GameMode::Bowling => "Pin score",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|mode| mode == self);
+        Self::ALL[(index.unwrap_or_default() + 1) % Self::ALL.len()]
+    }
+
+    pub fn stall_response(&self) -> StallResponse {
+        match self {
+            GameMode::Classic => StallResponse::EndRun,
+            GameMode::Relaxed | GameMode::Bowling => StallResponse::Wobble,
+        }
+    }
+}
+
+// Systems that read the `RaceSeed` when a race is spawned should run after this set
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(SystemSet)]
+pub struct RaceSeedSet;
+
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaceSeed>()
+            .init_resource::<RaceClock>()
+            .init_resource::<RunStats>()
+            .init_resource::<GameMode>()
+            .init_resource::<StallSettings>()
+            .init_resource::<StallWatchdog>()
+            .add_systems(
+                OnEnter(AppState::SpawningScene),
+                (
+                    (roll_race_seed, reset_race_clock).in_set(RaceSeedSet),
+                    reset_run_stats,
+                    reset_stall_watchdog,
+                    clear_game_over_reason,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    tick_race_clock,
+                    track_run_stats,
+                    watch_for_stalls,
+                    check_cheese_bounds,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Racing)),
+            );
+    }
+}
+
+// minutes and seconds, as race times are shown
+pub fn format_race_time(seconds: f32) -> String {
+    let seconds = seconds.max(0.) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn roll_race_seed(mut seed: ResMut<RaceSeed>, course: Option<Res<Course>>) {
+    seed.0 = if let Some(course) = course {
+        course.seed
+    } else {
+        rand::random()
+    };
+    info!("Race seed: {}", seed.0);
+}
+
+fn reset_race_clock(mut clock: ResMut<RaceClock>) {
+    clock.0 = 0.;
+}
+
+fn tick_race_clock(mut clock: ResMut<RaceClock>, time: Res<Time>) {
+    clock.0 += time.delta_seconds();
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{AppState, Cheese, Level, Person, TerrainChunk, TerrainNoise, RACE_START_Z};
+
+use super::RunStats;
+
+// Why the race ended, inserted by whichever system sends the game to `AppState::GameOver` and
+// removed when the next race is set up
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Resource)]
+pub enum GameOverReason {
+    // by the person whose hands reached the cheese
+    Caught(Person),
+    // the cheese came to a standstill
+    Stopped,
+    // the cheese left the world, either through the terrain or into non-finite space
+    FellOutOfWorld,
+    // the cheese strayed too far from the way down the hill
+    OffCourse,
+    CheeseDestroyed,
+}
+
+impl GameOverReason {
+    pub fn message(&self) -> String {
+        match self {
+            GameOverReason::Caught(person) => {
+                format!("The cheese was caught by {}!", person.describe())
+            }
+            GameOverReason::Stopped => "The cheese stopped rolling!".to_string(),
+            GameOverReason::FellOutOfWorld => "The cheese fell out of the world!".to_string(),
+            GameOverReason::OffCourse => "The cheese rolled off course!".to_string(),
+            GameOverReason::CheeseDestroyed => "The cheese fell to pieces!".to_string(),
+        }
+    }
+}
+
+pub(super) fn clear_game_over_reason(mut commands: Commands) {
+    commands.remove_resource::<GameOverReason>();
+}
+
+// how far below the surface of the hill the cheese can be before it has fallen through
+const FALL_DEPTH: f32 = 20.;
+// how far the cheese can stray sideways from the start line, or roll back up the hill
+const OFF_COURSE_WIDTH: f32 = 160.;
+const OFF_COURSE_ROLLBACK: f32 = 30.;
+
+pub(super) fn check_cheese_bounds(
+    mut commands: Commands,
+    cheese_query: Query<(&Transform, &LinearVelocity), With<Cheese>>,
+    level_query: Query<&Level>,
+    terrain_noise: Option<Res<TerrainNoise>>,
+    stats: Res<RunStats>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    let Ok((transform, velocity)) = cheese_query.get_single() else {
+        return;
+    };
+    let translation = transform.translation;
+    let fell_through = match (level_query.get_single(), terrain_noise) {
+        (Ok(level), Some(terrain_noise)) if translation.is_finite() => {
+            let surface = TerrainChunk::surface_position(
+                translation,
+                level.chunk_size,
+                level.quad_size,
+                &terrain_noise.get(),
+            );
+            translation.y < surface.y - FALL_DEPTH
+        }
+        _ => false,
+    };
+    let next_reason = if !translation.is_finite() || !velocity.0.is_finite() || fell_through {
+        GameOverReason::FellOutOfWorld
+    } else if translation.x.abs() > OFF_COURSE_WIDTH
+        || translation.z - RACE_START_Z < stats.distance - OFF_COURSE_ROLLBACK
+    {
+        GameOverReason::OffCourse
+    } else {
+        return;
+    };
+    info!("{}", next_reason.message());
+    commands.insert_resource(next_reason);
+    state.set(AppState::GameOver);
+}
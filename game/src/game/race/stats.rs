@@ -0,0 +1,89 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{Cheese, Obstacle, Person, PersonKnockedDown, RACE_START_Z};
+
+use super::RaceClock;
+
+// Numbers about the current run, shown when it ends
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct RunStats {
+    pub top_speed: f32,
+    // the furthest the cheese has made it down the hill
+    pub distance: f32,
+    pub time: f32,
+    pub ragdolls_dodged: u32,
+    pub obstacles_hit: u32,
+    // pursuers the cheese has bowled over
+    pub knockdowns: u32,
+    // ragdolls that have landed ahead of the cheese, which count as dodged once it passes them
+    pub(super) ragdolls_ahead: HashSet<Entity>,
+    pub(super) ragdolls_touched: HashSet<Entity>,
+}
+
+// ragdolls have to end up this far behind the cheese to count as dodged
+const DODGE_MARGIN: f32 = 5.;
+
+pub(super) fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(super) fn track_run_stats(
+    mut collisions: EventReader<CollisionStarted>,
+    mut knockdowns: EventReader<PersonKnockedDown>,
+    cheese_query: Query<(Entity, &Transform, &LinearVelocity), With<Cheese>>,
+    person_query: Query<(Entity, &Transform), (With<Person>, Without<Cheese>)>,
+    parent_query: Query<&Parent>,
+    obstacle_query: Query<(), With<Obstacle>>,
+    clock: Res<RaceClock>,
+    mut stats: ResMut<RunStats>,
+) {
+    let Ok((cheese, transform, velocity)) = cheese_query.get_single() else {
+        return;
+    };
+    if velocity.0.is_finite() {
+        stats.top_speed = stats.top_speed.max(velocity.0.length());
+    }
+    if transform.translation.is_finite() {
+        stats.distance = stats.distance.max(transform.translation.z - RACE_START_Z);
+    }
+    stats.time = clock.0;
+
+    let mut obstacles_hit = HashSet::new();
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let other = if *entity1 == cheese {
+            *entity2
+        } else if *entity2 == cheese {
+            *entity1
+        } else {
+            continue;
+        };
+        // obstacle parts and ragdoll limbs are children of the entity that marks them
+        let root = parent_query.get(other).map_or(other, Parent::get);
+        if obstacle_query.contains(root) || obstacle_query.contains(other) {
+            obstacles_hit.insert(root);
+        }
+        if person_query.contains(root) {
+            stats.ragdolls_touched.insert(root);
+        } else if person_query.contains(other) {
+            stats.ragdolls_touched.insert(other);
+        }
+    }
+    stats.obstacles_hit += obstacles_hit.len() as u32;
+    stats.knockdowns += knockdowns.read().count() as u32;
+
+    let cheese_z = transform.translation.z;
+    for (person, person_transform) in person_query.iter() {
+        let z = person_transform.translation.z;
+        if z > cheese_z {
+            stats.ragdolls_ahead.insert(person);
+        } else if z < cheese_z - DODGE_MARGIN && stats.ragdolls_ahead.remove(&person) {
+            // looped ragdolls come back as the same entity, so they can be dodged again
+            if !stats.ragdolls_touched.remove(&person) {
+                stats.ragdolls_dodged += 1;
+            }
+        }
+    }
+}
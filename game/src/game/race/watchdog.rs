@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{AppState, Cheese, CheeseContact};
+
+use super::{GameMode, GameOverReason, RaceClock};
+
+// How a stall is spotted and how hard the cheese is shaken loose
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct StallSettings {
+    // how many seconds of motion are looked back over
+    pub window: f32,
+    // the cheese is stalled if it stays within this distance of where it is for the whole window
+    pub min_travel: f32,
+    // or if its axis stays at least this aligned with the ground's normal, i.e. it is lying on its
+    // face, while it moves slower than `max_flat_speed`
+    pub flat_alignment: f32,
+    // a cheese sliding down the hill on its face is still racing
+    pub max_flat_speed: f32,
+    pub wobble_hop_speed: f32,
+    pub wobble_push_speed: f32,
+    pub wobble_spin_speed: f32,
+}
+
+impl Default for StallSettings {
+    fn default() -> Self {
+        Self {
+            window: 3.,
+            min_travel: 1.5,
+            flat_alignment: 0.9,
+            max_flat_speed: 3.,
+            wobble_hop_speed: 4.,
+            wobble_push_speed: 6.,
+            wobble_spin_speed: 4.,
+        }
+    }
+}
+
+// What to do once the cheese has stalled
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StallResponse {
+    EndRun,
+    // shake the cheese back upright and send it on its way
+    Wobble,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct StallSample {
+    time: f32,
+    position: Vec3,
+    flat: bool,
+}
+
+// The cheese's recent motion, kept for the length of the stall window
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct StallWatchdog {
+    samples: VecDeque<StallSample>,
+    pub recoveries: u32,
+}
+
+impl StallWatchdog {
+    fn record(&mut self, sample: StallSample, window: f32) {
+        self.samples.push_back(sample);
+        // keep one sample from before the window starts, so that it is known to be covered
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|next| next.time <= sample.time - window)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn is_stalled(&self, settings: &StallSettings) -> bool {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return false;
+        };
+        if last.time - first.time < settings.window {
+            return false;
+        }
+        let stuck = self
+            .samples
+            .iter()
+            .all(|sample| sample.position.distance(last.position) < settings.min_travel);
+        let flat = self.samples.iter().all(|sample| sample.flat);
+        stuck || flat
+    }
+}
+
+pub(super) fn reset_stall_watchdog(mut watchdog: ResMut<StallWatchdog>) {
+    *watchdog = StallWatchdog::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn watch_for_stalls(
+    mut commands: Commands,
+    mut cheese_query: Query<
+        (
+            &Transform,
+            &CheeseContact,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        With<Cheese>,
+    >,
+    mut watchdog: ResMut<StallWatchdog>,
+    settings: Res<StallSettings>,
+    mode: Res<GameMode>,
+    clock: Res<RaceClock>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    let Ok((transform, contact, mut linear_velocity, mut angular_velocity)) =
+        cheese_query.get_single_mut()
+    else {
+        return;
+    };
+    // the cheese's axis is its local Y, which points out of the hill when it lies on its face
+    let axis = transform.rotation * Vec3::Y;
+    let flat = contact.grounded
+        && axis.dot(contact.normal).abs() >= settings.flat_alignment
+        && linear_velocity.0.length() < settings.max_flat_speed;
+    watchdog.record(
+        StallSample {
+            time: clock.0,
+            position: transform.translation,
+            flat,
+        },
+        settings.window,
+    );
+    if !watchdog.is_stalled(&settings) {
+        return;
+    }
+
+    match mode.stall_response() {
+        StallResponse::EndRun => {
+            info!("The cheese stopped!");
+            commands.insert_resource(GameOverReason::Stopped);
+            state.set(AppState::GameOver);
+        }
+        StallResponse::Wobble => {
+            info!("Wobbling the cheese loose");
+            // tip the axis back towards lying across the hill, so the cheese stands on its rim
+            let across = Vec3::X * axis.x.signum();
+            angular_velocity.0 =
+                axis.cross(across).normalize_or_zero() * settings.wobble_spin_speed;
+            linear_velocity.0 +=
+                Vec3::Y * settings.wobble_hop_speed + Vec3::Z * settings.wobble_push_speed;
+            watchdog.samples.clear();
+            watchdog.recoveries += 1;
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{AppState, Cheese, GameMode, RunStats};
+
+mod style;
+pub use style::*;
+
+mod systems;
+use systems::*;
+
+// where the cheese starts rolling from, which distance is measured from
+pub const RACE_START_Z: f32 = 50.;
+// what each flattened pursuer is worth in bowling
+pub const PIN_POINTS: f32 = 100.;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub struct Score(pub f32);
+// The best score in each game mode, since distance and pins don't compare
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct HighScore(pub HashMap<GameMode, f32>);
+
+impl HighScore {
+    pub fn get(&self, mode: GameMode) -> f32 {
+        self.0.get(&mode).copied().unwrap_or_default()
+    }
+
+    pub fn record(&mut self, mode: GameMode, score: f32) {
+        if score > self.get(mode) {
+            self.0.insert(mode, score);
+        }
+    }
+}
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .init_resource::<HighScore>()
+            .init_resource::<StyleScore>()
+            .add_event::<StyleEvent>()
+            .add_systems(OnEnter(AppState::SpawningScene), reset_style_score)
+            .add_systems(
+                Update,
+                (
+                    (
+                        tick_combo,
+                        detect_near_misses,
+                        award_knockdowns,
+                        award_airtime,
+                    )
+                        .chain(),
+                    track_score,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Racing)),
+            )
+            .add_systems(OnExit(AppState::GameOver), update_scores);
+    }
+}
+
+// the distance travelled down the hill, or the pursuers flattened in bowling, plus any style points
+fn track_score(
+    mut score: ResMut<Score>,
+    style: Res<StyleScore>,
+    stats: Res<RunStats>,
+    mode: Res<GameMode>,
+    cheese_query: Query<&Transform, With<Cheese>>,
+) {
+    let Ok(transform) = cheese_query.get_single() else {
+        return;
+    };
+
+    let base = match *mode {
+        GameMode::Classic | GameMode::Relaxed => transform.translation.z - RACE_START_Z,
+        GameMode::Bowling => stats.knockdowns as f32 * PIN_POINTS,
+    };
+    score.0 = base + style.total;
+}
+
+fn update_scores(mut score: ResMut<Score>, mut high_score: ResMut<HighScore>, mode: Res<GameMode>) {
+    high_score.record(*mode, score.0);
+    score.0 = 0.;
+}
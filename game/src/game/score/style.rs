@@ -0,0 +1,97 @@
+use bevy::{prelude::*, utils::HashMap};
+
+// The tricks that earn style points
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum StyleSource {
+    // passing close to a wall or ragdoll without touching it
+    NearMiss,
+    // time spent in the air, paid out on landing
+    Airtime,
+    // bowling a ragdoll over
+    Knockdown,
+}
+
+impl StyleSource {
+    pub const ALL: [StyleSource; 3] = [
+        StyleSource::NearMiss,
+        StyleSource::Airtime,
+        StyleSource::Knockdown,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StyleSource::NearMiss => "Near miss",
+            StyleSource::Airtime => "Airtime",
+            StyleSource::Knockdown => "Knockdown",
+        }
+    }
+}
+
+// Sent for every trick, with the points it earned after the combo multiplier
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct StyleEvent {
+    pub source: StyleSource,
+    pub points: f32,
+    pub multiplier: u32,
+}
+
+// How many times a trick was pulled off in a race and what it earned
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StyleTally {
+    pub count: u32,
+    pub points: f32,
+}
+
+// The style points earned in the current race, on top of the distance travelled
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct StyleScore {
+    pub total: f32,
+    pub breakdown: HashMap<StyleSource, StyleTally>,
+    // how many tricks have been chained together without the combo window running out
+    pub combo: u32,
+    pub best_combo: u32,
+    combo_timer: Timer,
+    // hazards near the cheese right now, and whether the cheese has touched them
+    pub(super) nearby_hazards: HashMap<Entity, bool>,
+}
+
+impl StyleScore {
+    // tricks within this many seconds of each other build a combo
+    pub const COMBO_WINDOW: f32 = 3.;
+    pub const MAX_MULTIPLIER: u32 = 5;
+
+    pub fn multiplier(&self) -> u32 {
+        self.combo.clamp(1, Self::MAX_MULTIPLIER)
+    }
+
+    pub fn tally(&self, source: StyleSource) -> StyleTally {
+        self.breakdown.get(&source).copied().unwrap_or_default()
+    }
+
+    // scores a trick, extending the combo, and returns the event describing it
+    pub fn award(&mut self, source: StyleSource, base_points: f32) -> StyleEvent {
+        self.combo += 1;
+        self.best_combo = self.best_combo.max(self.combo);
+        self.combo_timer = Timer::from_seconds(Self::COMBO_WINDOW, TimerMode::Once);
+        let multiplier = self.multiplier();
+        let points = base_points * multiplier as f32;
+        self.total += points;
+        let tally = self.breakdown.entry(source).or_default();
+        tally.count += 1;
+        tally.points += points;
+        StyleEvent {
+            source,
+            points,
+            multiplier,
+        }
+    }
+
+    pub(super) fn tick(&mut self, delta: std::time::Duration) {
+        if self.combo > 0 && self.combo_timer.tick(delta).finished() {
+            self.combo = 0;
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::{
+    Cheese, CheeseCondition, CheeseKind, CheeseLanding, Obstacle, Person, PersonKnockedDown,
+    PooledRagdoll,
+};
+
+use super::{StyleEvent, StyleScore, StyleSource};
+
+// how close a hazard has to pass outside the rim of the cheese to count as a near miss
+const NEAR_MISS_DISTANCE: f32 = 1.5;
+// dawdling past a wall isn't very stylish
+const NEAR_MISS_MIN_SPEED: f32 = 8.;
+const NEAR_MISS_POINTS: f32 = 10.;
+// short bounces off bumps in the hill don't count as airtime, and neither do hops, since they
+// could be done over and over
+const MIN_AIRTIME: f32 = 0.5;
+const AIRTIME_POINTS_PER_SECOND: f32 = 20.;
+const KNOCKDOWN_POINTS: f32 = 25.;
+
+type HazardFilter = Or<(With<Obstacle>, With<Person>)>;
+
+// obstacle parts and ragdoll limbs are children of the entity that marks them
+fn hazard_root(
+    entity: Entity,
+    parent_query: &Query<&Parent>,
+    hazard_query: &Query<(), HazardFilter>,
+) -> Option<Entity> {
+    if hazard_query.contains(entity) {
+        return Some(entity);
+    }
+    parent_query
+        .get(entity)
+        .ok()
+        .map(Parent::get)
+        .filter(|parent| hazard_query.contains(*parent))
+}
+
+fn collided_with_cheese(collision: &CollisionStarted, cheese: Entity) -> Option<Entity> {
+    let CollisionStarted(entity1, entity2) = *collision;
+    if entity1 == cheese {
+        Some(entity2)
+    } else if entity2 == cheese {
+        Some(entity1)
+    } else {
+        None
+    }
+}
+
+pub(super) fn reset_style_score(mut style: ResMut<StyleScore>) {
+    *style = StyleScore::default();
+}
+
+pub(super) fn tick_combo(mut style: ResMut<StyleScore>, time: Res<Time>) {
+    style.tick(time.delta());
+}
+
+// hazards are tracked from when they come close to the cheese until they leave again, and only
+// count if the cheese never touched them in between
+#[allow(clippy::too_many_arguments)]
+pub(super) fn detect_near_misses(
+    spatial_query: SpatialQuery,
+    mut collisions: EventReader<CollisionStarted>,
+    cheese_query: Query<
+        (
+            Entity,
+            &CheeseKind,
+            &CheeseCondition,
+            &Transform,
+            &LinearVelocity,
+        ),
+        With<Cheese>,
+    >,
+    parent_query: Query<&Parent>,
+    hazard_query: Query<(), HazardFilter>,
+    pooled_query: Query<(), With<PooledRagdoll>>,
+    mut style: ResMut<StyleScore>,
+    mut events: EventWriter<StyleEvent>,
+) {
+    let Ok((cheese, kind, condition, transform, velocity)) = cheese_query.get_single() else {
+        return;
+    };
+    // hazards that were despawned or sent back to the pool didn't get passed, they just vanished
+    style
+        .nearby_hazards
+        .retain(|root, _| hazard_query.contains(*root) && !pooled_query.contains(*root));
+    for collision in collisions.read() {
+        let Some(other) = collided_with_cheese(collision, cheese) else {
+            continue;
+        };
+        if let Some(root) = hazard_root(other, &parent_query, &hazard_query) {
+            style.nearby_hazards.insert(root, true);
+        }
+    }
+
+    let reach = kind.stats().radius * condition.scale() + NEAR_MISS_DISTANCE;
+    let nearby = spatial_query
+        .shape_intersections(
+            &Collider::ball(reach),
+            transform.translation,
+            Quat::IDENTITY,
+            SpatialQueryFilter::default().without_entities([cheese]),
+        )
+        .into_iter()
+        .filter_map(|entity| hazard_root(entity, &parent_query, &hazard_query))
+        .filter(|root| !pooled_query.contains(*root))
+        .collect::<HashSet<Entity>>();
+    for root in nearby.iter() {
+        style.nearby_hazards.entry(*root).or_insert(false);
+    }
+
+    let passed = style
+        .nearby_hazards
+        .iter()
+        .filter(|(root, _)| !nearby.contains(*root))
+        .map(|(root, touched)| (*root, *touched))
+        .collect::<Vec<_>>();
+    for (root, touched) in passed {
+        style.nearby_hazards.remove(&root);
+        if !touched && velocity.0.length() >= NEAR_MISS_MIN_SPEED {
+            events.send(style.award(StyleSource::NearMiss, NEAR_MISS_POINTS));
+        }
+    }
+}
+
+// a person only goes down again once they have got back up, so each knockdown counts
+pub(super) fn award_knockdowns(
+    mut knockdowns: EventReader<PersonKnockedDown>,
+    mut style: ResMut<StyleScore>,
+    mut events: EventWriter<StyleEvent>,
+) {
+    for _ in knockdowns.read() {
+        events.send(style.award(StyleSource::Knockdown, KNOCKDOWN_POINTS));
+    }
+}
+
+pub(super) fn award_airtime(
+    mut landings: EventReader<CheeseLanding>,
+    mut style: ResMut<StyleScore>,
+    mut events: EventWriter<StyleEvent>,
+) {
+    for landing in landings.read() {
+        if !landing.hopped && landing.airtime >= MIN_AIRTIME {
+            let points = (landing.airtime * AIRTIME_POINTS_PER_SECOND).round();
+            events.send(style.award(StyleSource::Airtime, points));
+        }
+    }
+}
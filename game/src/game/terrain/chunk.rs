@@ -6,7 +6,7 @@ use bevy::{
 };
 use bevy_xpbd_3d::prelude::*;
 
-use crate::{Chunk, TextureAssets, Vertex};
+use crate::{Chunk, GameLayer, TextureAssets, Vertex};
 
 #[derive(Debug, Clone, Default)]
 #[derive(Component)]
@@ -36,6 +36,81 @@ impl TerrainChunk {
         ]
     }
 
+    // the position of a vertex relative to the chunk's translation, sloping the noise down the hill
+    pub fn vertex_position(&self, local_vertex: Vertex, noise: &impl NoiseFn<f64, 2>) -> Vec3 {
+        let slope = Quat::from_rotation_x(std::f32::consts::FRAC_PI_4);
+
+        let global_vertex = self.chunk.to_global_coords(local_vertex);
+        let noise_sample = noise.get([global_vertex.x as f64, global_vertex.z as f64]) as f32;
+        let sloped_noise = slope * Vec3::new(0., noise_sample, 0.);
+        let position = self.chunk.to_translation(local_vertex);
+        let sloped_position = Vec3::new(position.x, -position.y, position.y);
+        let unsloped_position = Vec3::new(position.x, 0., position.y);
+        let target_position = sloped_position + sloped_noise;
+
+        match self.chunk.origin.z.cmp(&0) {
+            std::cmp::Ordering::Less => target_position,
+            std::cmp::Ordering::Equal => {
+                // blend between 0 and the noise
+                let chunk_z_ratio =
+                    (self.chunk.size.z as f32 - local_vertex.z as f32) / self.chunk.size.z as f32;
+                target_position.lerp(unsloped_position, chunk_z_ratio)
+            }
+            std::cmp::Ordering::Greater => unsloped_position,
+        }
+    }
+
+    // the translation of the chunk's mesh in the world
+    pub fn translation(&self) -> Vec3 {
+        let x = self.chunk.origin.x as f32 * self.chunk.size.x as f32 * self.chunk.quad_size.x;
+        let y = (self.chunk.origin.z as f32).clamp(std::f32::NEG_INFINITY, 0.)
+            * self.chunk.size.z as f32
+            * self.chunk.quad_size.y;
+        let z = -(self.chunk.origin.z as f32 * self.chunk.size.z as f32) * self.chunk.quad_size.y;
+        Vec3::new(x, y, z)
+    }
+
+    // the point on the terrain at the vertex nearest to a translation's x and z
+    pub fn surface_position(
+        translation: Vec3,
+        chunk_size: Vertex,
+        quad_size: Vec2,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> Vec3 {
+        let chunk_length = Vec2::new(
+            chunk_size.x as f32 * quad_size.x,
+            chunk_size.z as f32 * quad_size.y,
+        );
+        // level chunks count z up the hill, against the world's z axis
+        let origin = Vertex::new(
+            (translation.x / chunk_length.x).floor() as i32,
+            -(translation.z / chunk_length.y).floor() as i32,
+        );
+        let terrain_chunk = Self::new(Chunk::new(origin, chunk_size, quad_size));
+        let local_vertex = Vertex::new(
+            ((translation.x / quad_size.x).round() as i32 - origin.x * chunk_size.x)
+                .clamp(0, chunk_size.x),
+            ((translation.z / quad_size.y).round() as i32 + origin.z * chunk_size.z)
+                .clamp(0, chunk_size.z),
+        );
+        terrain_chunk.translation() + terrain_chunk.vertex_position(local_vertex, noise)
+    }
+
+    // the terrain's up direction around a translation, from the neighbouring vertices
+    pub fn surface_normal(
+        translation: Vec3,
+        chunk_size: Vertex,
+        quad_size: Vec2,
+        noise: &impl NoiseFn<f64, 2>,
+    ) -> Vec3 {
+        let sample = |offset: Vec3| {
+            Self::surface_position(translation + offset, chunk_size, quad_size, noise)
+        };
+        let along_x = sample(Vec3::X * quad_size.x) - sample(Vec3::NEG_X * quad_size.x);
+        let along_z = sample(Vec3::Z * quad_size.y) - sample(Vec3::NEG_Z * quad_size.y);
+        along_z.cross(along_x).try_normalize().unwrap_or(Vec3::Y)
+    }
+
     pub fn generate_mesh(&self, noise: &impl NoiseFn<f64, 2>) -> Mesh {
         let num_vertices = self.chunk.count_vertices() as usize;
         let num_indices = self.chunk.count_indices() as usize;
@@ -45,39 +120,10 @@ impl TerrainChunk {
         // Each row is (M - 1) X (N-1) quads
         let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
 
-        let slope = Quat::from_rotation_x(std::f32::consts::FRAC_PI_4);
-
         for vertex in self.chunk.iter_by_row() {
             let global_vertex = self.chunk.to_global_coords(vertex);
-            let noise_sample = noise.get([global_vertex.x as f64, global_vertex.z as f64]) as f32;
-            let sloped_noise = slope * Vec3::new(0., noise_sample, 0.);
-            let position = self.chunk.to_translation(vertex);
-            let sloped_position = Vec3::new(position.x, -position.y, position.y);
-            let unsloped_position = Vec3::new(position.x, 0., position.y);
-            let target_position = sloped_position + sloped_noise;
-
-            match self.chunk.origin.z.cmp(&0) {
-                std::cmp::Ordering::Less => {
-                    positions.push(target_position.to_array());
-                    normals.push(Vec3::Y.to_array());
-                }
-                std::cmp::Ordering::Equal => {
-                    // blend between 0 and the noise
-                    let chunk_z_ratio =
-                        (self.chunk.size.z as f32 - vertex.z as f32) / self.chunk.size.z as f32;
-
-                    positions.push(
-                        target_position
-                            .lerp(unsloped_position, chunk_z_ratio)
-                            .to_array(),
-                    );
-                    normals.push(Vec3::Y.to_array());
-                }
-                std::cmp::Ordering::Greater => {
-                    positions.push(unsloped_position.to_array());
-                    normals.push(Vec3::Y.to_array());
-                }
-            }
+            positions.push(self.vertex_position(vertex, noise).to_array());
+            normals.push(Vec3::Y.to_array());
 
             uvs.push([global_vertex.z as f32 / 8., global_vertex.x as f32 / 8.]);
 
@@ -101,11 +147,7 @@ impl TerrainChunk {
         materials: &mut Assets<StandardMaterial>,
     ) -> impl Bundle {
         let mesh = self.generate_mesh(noise);
-        let x = self.chunk.origin.x as f32 * self.chunk.size.x as f32 * self.chunk.quad_size.x;
-        let y = (self.chunk.origin.z as f32).clamp(std::f32::NEG_INFINITY, 0.)
-            * self.chunk.size.z as f32
-            * self.chunk.quad_size.y;
-        let z = -(self.chunk.origin.z as f32 * self.chunk.size.z as f32) * self.chunk.quad_size.y;
+        let translation = self.translation();
         (
             Name::new(format!(
                 "Terrain Chunk {}x{}",
@@ -114,6 +156,7 @@ impl TerrainChunk {
             RigidBody::Static,
             ColliderDensity(1e7),
             AsyncCollider(ComputedCollider::TriMesh),
+            GameLayer::terrain(),
             PbrBundle {
                 mesh: meshes.add(mesh),
                 material: materials.add(StandardMaterial {
@@ -123,7 +166,7 @@ impl TerrainChunk {
                     depth_map: Some(textures.ground_displacement.clone()),
                     ..Default::default()
                 }),
-                transform: Transform::from_xyz(x, y, z),
+                transform: Transform::from_translation(translation),
                 ..Default::default()
             },
             self,
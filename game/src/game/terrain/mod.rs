@@ -30,6 +30,15 @@ impl Terrain {
         Name::new("Terrain")
     }
 
+    // despawn every chunk so that the next update regenerates them, e.g. after the noise changes
+    pub fn clear(&mut self, commands: &mut Commands) {
+        for (_, entities) in self.chunk_entities.drain() {
+            for entity in entities {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
     pub fn update(
         &mut self,
         level: &Level,
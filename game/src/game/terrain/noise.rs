@@ -3,8 +3,12 @@
 
 use noise::{Billow, Blend, Fbm, MultiFractal, NoiseFn, Perlin, RidgedMulti, ScaleBias};
 
+use serde::{Deserialize, Serialize};
+
 use bevy::prelude::*;
 
+use crate::Vertex;
+
 #[derive(Resource)]
 pub struct TerrainNoise(Box<dyn NoiseFn<f64, 2> + Send + Sync>);
 
@@ -17,6 +21,14 @@ impl TerrainNoise {
         Self(Box::new(noise))
     }
 
+    // the seeded terrain with hand-painted bumps and dips layered on top
+    pub fn with_modifiers(seed: u32, modifiers: Vec<HeightModifier>) -> Self {
+        Self::from_noise(ModifiedNoise {
+            noise: generate_terrain_noise(seed),
+            modifiers,
+        })
+    }
+
     pub fn get(&self) -> &dyn NoiseFn<f64, 2> {
         &self.0
     }
@@ -38,7 +50,7 @@ fn generate_terrain_noise(seed: u32) -> impl NoiseFn<f64, 2> {
     )
     .set_bias(1.0);
     let hilly_ridged_multi = ScaleBias::new(
-        RidgedMulti::<Perlin>::new(seed + 17)
+        RidgedMulti::<Perlin>::new(seed.wrapping_add(17))
             .set_frequency(2.0)
             .set_lacunarity(2.162109375)
             .set_octaves(1),
@@ -47,7 +59,55 @@ fn generate_terrain_noise(seed: u32) -> impl NoiseFn<f64, 2> {
     ScaleBias::new(Blend::new(
         hilly_billow,
         hilly_ridged_multi,
-        Fbm::<Perlin>::new(seed + 19).set_frequency(0.001),
+        Fbm::<Perlin>::new(seed.wrapping_add(19)).set_frequency(0.001),
     ))
     .set_scale(3.)
 }
+
+// A smooth bump (or dip, when height is negative) added to the terrain noise around a vertex
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct HeightModifier {
+    // in the same global vertex coordinates that the terrain samples noise with
+    pub vertex: Vertex,
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl HeightModifier {
+    pub fn new(vertex: Vertex, radius: f32, height: f32) -> Self {
+        Self {
+            vertex,
+            radius,
+            height,
+        }
+    }
+
+    pub fn sample(&self, point: [f64; 2]) -> f64 {
+        let dx = point[0] - self.vertex.x as f64;
+        let dz = point[1] - self.vertex.z as f64;
+        let distance_ratio = (dx * dx + dz * dz).sqrt() / self.radius as f64;
+        if distance_ratio >= 1. {
+            return 0.;
+        }
+        // squared falloff keeps the edges of the bump flush with the surrounding terrain
+        let falloff = 1. - distance_ratio * distance_ratio;
+        self.height as f64 * falloff * falloff
+    }
+}
+
+struct ModifiedNoise<N> {
+    noise: N,
+    modifiers: Vec<HeightModifier>,
+}
+
+impl<N: NoiseFn<f64, 2>> NoiseFn<f64, 2> for ModifiedNoise<N> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.noise.get(point)
+            + self
+                .modifiers
+                .iter()
+                .map(|modifier| modifier.sample(point))
+                .sum::<f64>()
+    }
+}
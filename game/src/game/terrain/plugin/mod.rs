@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{AppState, TerrainNoise, TextureAssets};
+use crate::{AppState, RaceSeedSet, TerrainNoise, TextureAssets};
 
 mod systems;
 
@@ -15,7 +15,10 @@ impl Plugin for TerrainPlugin {
                 resource_exists::<TextureAssets>().and_then(resource_exists::<TerrainNoise>()),
             ),
         )
-        .add_systems(OnEnter(AppState::SpawningScene), systems::seed_noise)
+        .add_systems(
+            OnEnter(AppState::SpawningScene),
+            systems::seed_noise.after(RaceSeedSet),
+        )
         .add_systems(Update, systems::attach_terrain);
     }
 }
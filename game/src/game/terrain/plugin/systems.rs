@@ -1,9 +1,13 @@
 use bevy::prelude::*;
 
-use crate::{Level, Terrain, TerrainNoise, TextureAssets};
+use crate::{Course, Level, RaceSeed, Terrain, TerrainNoise, TextureAssets};
 
-pub(super) fn seed_noise(mut commands: Commands) {
-    commands.insert_resource(TerrainNoise::new(rand::random()));
+pub(super) fn seed_noise(mut commands: Commands, seed: Res<RaceSeed>, course: Option<Res<Course>>) {
+    if let Some(course) = course {
+        commands.insert_resource(course.terrain_noise());
+    } else {
+        commands.insert_resource(TerrainNoise::new(seed.0));
+    }
 }
 
 pub(super) fn attach_terrain(mut commands: Commands, query: Query<Entity, Added<Level>>) {
@@ -3,6 +3,12 @@ use bevy::{asset::AssetMetaCheck, prelude::*};
 mod assets;
 pub use assets::*;
 
+mod editor;
+pub use editor::*;
+
+mod export;
+pub use export::*;
+
 mod game;
 pub use game::*;
 
@@ -27,6 +33,7 @@ pub enum AppState {
     Countdown,
     Racing,
     GameOver,
+    Editor,
 }
 
 pub fn run_app(canvas: Option<String>) {
@@ -48,6 +55,9 @@ pub fn run_app(canvas: Option<String>) {
             TerrainPlugin,
             ObstaclesPlugin,
             MenuPlugin,
+            // courses are saved to disk, which the web build doesn't have
+            #[cfg(not(target_arch = "wasm32"))]
+            EditorPlugin,
         ))
         .run();
 }
@@ -1,15 +1,30 @@
 use bevy::prelude::*;
 
-use crate::{Cheese, SceneAssets};
+use crate::{Cheese, CheeseKind, SceneAssets, SelectedCheese};
 
 #[derive(Component)]
 pub(super) struct MenuGraphic;
+// The ring of cheeses on display, which turns to bring the selected cheese to the front
 #[derive(Component)]
-pub(super) struct MenuGraphicCheese;
+pub(super) struct MenuCarousel;
+#[derive(Component)]
+pub(super) struct MenuGraphicCheese(CheeseKind);
+
+const CAROUSEL_RADIUS: f32 = 2.5;
+
+// how far the carousel turns to bring a cheese to the front
+fn carousel_angle(kind: CheeseKind) -> f32 {
+    let index = CheeseKind::ALL
+        .iter()
+        .position(|other| *other == kind)
+        .unwrap_or_default();
+    -(index as f32) * std::f32::consts::TAU / CheeseKind::ALL.len() as f32
+}
 
 pub(super) fn spawn_graphic(
     mut commands: Commands,
     scenes: Res<SceneAssets>,
+    selected_cheese: Res<SelectedCheese>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
@@ -20,15 +35,37 @@ pub(super) fn spawn_graphic(
             SpatialBundle::default(),
         ))
         .with_children(|builder| {
-            // cheese
-            builder.spawn((
-                Name::new("Cheese"),
-                MenuGraphicCheese,
-                Cheese::graphic(
-                    Transform::from_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
-                    &scenes,
-                ),
-            ));
+            // cheese, spread around a ring that puts the selected one under the spotlights
+            builder
+                .spawn((
+                    Name::new("Cheese Carousel"),
+                    MenuCarousel,
+                    SpatialBundle::from_transform(
+                        Transform::from_xyz(0., 0., -CAROUSEL_RADIUS).with_rotation(
+                            Quat::from_rotation_y(carousel_angle(selected_cheese.0)),
+                        ),
+                    ),
+                ))
+                .with_children(|builder| {
+                    for kind in CheeseKind::ALL {
+                        let angle = -carousel_angle(kind);
+                        // rest each cheese on the floor
+                        let lift = kind.stats().radius - Cheese::RADIUS;
+                        builder.spawn((
+                            Name::new(kind.label()),
+                            MenuGraphicCheese(kind),
+                            Cheese::graphic(
+                                kind,
+                                Transform::from_translation(
+                                    Quat::from_rotation_y(angle) * Vec3::Z * CAROUSEL_RADIUS
+                                        + Vec3::Y * lift,
+                                )
+                                .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                                &scenes,
+                            ),
+                        ));
+                    }
+                });
             // lighting
             let spotlight = SpotLight {
                 color: Color::rgb(0.93, 0.85, 0.74),
@@ -69,9 +106,29 @@ pub(super) fn spawn_graphic(
         });
 }
 
-pub(super) fn spin_graphic(mut query: Query<&mut Transform, With<MenuGraphicCheese>>) {
+// only the cheese at the front of the carousel spins
+pub(super) fn spin_graphic(
+    mut query: Query<(&mut Transform, &MenuGraphicCheese)>,
+    selected_cheese: Res<SelectedCheese>,
+) {
     const ROTATION_SPEED: f32 = std::f32::consts::FRAC_PI_8 * 0.03;
+    for (mut transform, cheese) in query.iter_mut() {
+        if cheese.0 == selected_cheese.0 {
+            transform.rotate_y(ROTATION_SPEED);
+        }
+    }
+}
+
+pub(super) fn turn_carousel(
+    mut query: Query<&mut Transform, With<MenuCarousel>>,
+    selected_cheese: Res<SelectedCheese>,
+    time: Res<Time>,
+) {
+    const TURN_SPEED: f32 = 6.;
+    let target = Quat::from_rotation_y(carousel_angle(selected_cheese.0));
     for mut transform in query.iter_mut() {
-        transform.rotate_y(ROTATION_SPEED);
+        transform.rotation = transform
+            .rotation
+            .slerp(target, (TURN_SPEED * time.delta_seconds()).min(1.));
     }
 }
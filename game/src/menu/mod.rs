@@ -28,7 +28,21 @@ impl Plugin for MenuPlugin {
             )
                 .chain(),
         )
-        .add_systems(Update, (spin_graphic, handle_play));
+        .add_systems(
+            Update,
+            (
+                spin_graphic,
+                turn_carousel,
+                handle_play,
+                #[cfg(not(target_arch = "wasm32"))]
+                handle_editor,
+                handle_difficulty,
+                handle_game_mode,
+                handle_biome,
+                // the steering keys also turn the carousel, so only listen while in the menu
+                handle_cheese_selection.run_if(in_state(AppState::Menu)),
+            ),
+        );
     }
 }
 
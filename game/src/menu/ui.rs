@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{button, AppState, FontAssets};
+use crate::{
+    button, AppState, Biome, DifficultyCurve, FontAssets, GameMode, ObstacleRegistry,
+    SelectedCheese,
+};
 
 const GAME_TITLE: &str = "Cheese Rolling: Forever";
 
@@ -8,6 +11,27 @@ const GAME_TITLE: &str = "Cheese Rolling: Forever";
 pub(super) struct MenuUI;
 #[derive(Component)]
 pub(super) struct PlayButton;
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Component)]
+pub(super) struct EditorButton;
+#[derive(Component)]
+pub(super) struct DifficultyButton;
+#[derive(Component)]
+pub(super) struct DifficultyButtonText;
+#[derive(Component)]
+pub(super) struct GameModeButton;
+#[derive(Component)]
+pub(super) struct GameModeButtonText;
+#[derive(Component)]
+pub(super) struct BiomeButton;
+#[derive(Component)]
+pub(super) struct BiomeButtonText;
+#[derive(Component)]
+pub(super) struct PreviousCheeseButton;
+#[derive(Component)]
+pub(super) struct NextCheeseButton;
+#[derive(Component)]
+pub(super) struct CheeseNameText;
 
 pub(super) fn handle_play(
     interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
@@ -20,7 +44,119 @@ pub(super) fn handle_play(
     }
 }
 
-pub(super) fn spawn_start_menu(mut commands: Commands, fonts: Res<FontAssets>) {
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn handle_editor(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<EditorButton>)>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            state.set(AppState::Editor);
+        }
+    }
+}
+
+// cycles through the difficulty presets
+pub(super) fn handle_difficulty(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<DifficultyButton>)>,
+    mut text_query: Query<&mut Text, With<DifficultyButtonText>>,
+    difficulty: Res<DifficultyCurve>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            let next = DifficultyCurve::preset(difficulty.preset.unwrap_or_default().next());
+            for mut text in text_query.iter_mut() {
+                text.sections[0].value = difficulty_label(&next);
+            }
+            commands.insert_resource(next);
+        }
+    }
+}
+
+// cycles through the game modes
+pub(super) fn handle_game_mode(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<GameModeButton>)>,
+    mut text_query: Query<&mut Text, With<GameModeButtonText>>,
+    mut mode: ResMut<GameMode>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            *mode = mode.next();
+            for mut text in text_query.iter_mut() {
+                text.sections[0].value = game_mode_label(&mode);
+            }
+        }
+    }
+}
+
+// cycles through the biomes, and the obstacles generated on each
+pub(super) fn handle_biome(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<BiomeButton>)>,
+    mut text_query: Query<&mut Text, With<BiomeButtonText>>,
+    mut biome: ResMut<Biome>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = interaction {
+            *biome = biome.next();
+            for mut text in text_query.iter_mut() {
+                text.sections[0].value = biome_label(&biome);
+            }
+            commands.insert_resource(ObstacleRegistry::for_biome(*biome));
+        }
+    }
+}
+
+// turns the cheese carousel with its arrow buttons or the steering keys
+pub(super) fn handle_cheese_selection(
+    inputs: Res<Input<KeyCode>>,
+    previous_query: Query<&Interaction, (Changed<Interaction>, With<PreviousCheeseButton>)>,
+    next_query: Query<&Interaction, (Changed<Interaction>, With<NextCheeseButton>)>,
+    mut text_query: Query<&mut Text, With<CheeseNameText>>,
+    mut selected_cheese: ResMut<SelectedCheese>,
+) {
+    let previous = previous_query
+        .iter()
+        .any(|interaction| matches!(interaction, Interaction::Pressed));
+    let next = next_query
+        .iter()
+        .any(|interaction| matches!(interaction, Interaction::Pressed));
+    if previous || inputs.any_just_pressed([KeyCode::Left, KeyCode::A]) {
+        selected_cheese.0 = selected_cheese.0.previous();
+    } else if next || inputs.any_just_pressed([KeyCode::Right, KeyCode::D]) {
+        selected_cheese.0 = selected_cheese.0.next();
+    }
+    if selected_cheese.is_changed() {
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = selected_cheese.0.label().to_string();
+        }
+    }
+}
+
+fn difficulty_label(difficulty: &DifficultyCurve) -> String {
+    match difficulty.preset {
+        Some(preset) => format!("Difficulty: {}", preset.label()),
+        None => "Difficulty: Custom".to_string(),
+    }
+}
+
+fn game_mode_label(mode: &GameMode) -> String {
+    format!("Mode: {}", mode.label())
+}
+
+fn biome_label(biome: &Biome) -> String {
+    format!("Biome: {}", biome.label())
+}
+
+pub(super) fn spawn_start_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    difficulty: Res<DifficultyCurve>,
+    mode: Res<GameMode>,
+    biome: Res<Biome>,
+    selected_cheese: Res<SelectedCheese>,
+) {
     commands
         .spawn((
             Name::new("Menu UI"),
@@ -55,12 +191,32 @@ pub(super) fn spawn_start_menu(mut commands: Commands, fonts: Res<FontAssets>) {
                 .with_children(|builder| {
                     builder.spawn((Name::new("Title"), title_node(fonts.title.clone())));
                     builder
-                        .spawn((Name::new("Play Button"), PlayButton, button()))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Name::new("Play Button Text"),
+                        .spawn((
+                            Name::new("Cheese Selection"),
+                            NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(12.),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|builder| {
+                            builder
+                                .spawn((
+                                    Name::new("Previous Cheese Button"),
+                                    PreviousCheeseButton,
+                                    arrow_button(),
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(button_text("<"));
+                                });
+                            builder.spawn((
+                                Name::new("Cheese Name Text"),
+                                CheeseNameText,
                                 TextBundle::from_section(
-                                    "Play",
+                                    selected_cheese.0.label(),
                                     TextStyle {
                                         font_size: 40.0,
                                         color: Color::rgb(0.9, 0.9, 0.9),
@@ -68,6 +224,109 @@ pub(super) fn spawn_start_menu(mut commands: Commands, fonts: Res<FontAssets>) {
                                     },
                                 ),
                             ));
+                            builder
+                                .spawn((
+                                    Name::new("Next Cheese Button"),
+                                    NextCheeseButton,
+                                    arrow_button(),
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(button_text(">"));
+                                });
+                        });
+                    builder
+                        .spawn((
+                            Name::new("Menu Buttons"),
+                            NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(12.),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|builder| {
+                            builder
+                                .spawn((Name::new("Play Button"), PlayButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Play Button Text"),
+                                        TextBundle::from_section(
+                                            "Play",
+                                            TextStyle {
+                                                font_size: 40.0,
+                                                color: Color::rgb(0.9, 0.9, 0.9),
+                                                ..Default::default()
+                                            },
+                                        ),
+                                    ));
+                                });
+                            // the editor isn't part of the web build
+                            #[cfg(not(target_arch = "wasm32"))]
+                            builder
+                                .spawn((Name::new("Editor Button"), EditorButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Editor Button Text"),
+                                        TextBundle::from_section(
+                                            "Editor",
+                                            TextStyle {
+                                                font_size: 40.0,
+                                                color: Color::rgb(0.9, 0.9, 0.9),
+                                                ..Default::default()
+                                            },
+                                        ),
+                                    ));
+                                });
+                            builder
+                                .spawn((Name::new("Difficulty Button"), DifficultyButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Difficulty Button Text"),
+                                        DifficultyButtonText,
+                                        TextBundle::from_section(
+                                            difficulty_label(&difficulty),
+                                            TextStyle {
+                                                font_size: 40.0,
+                                                color: Color::rgb(0.9, 0.9, 0.9),
+                                                ..Default::default()
+                                            },
+                                        ),
+                                    ));
+                                });
+                            builder
+                                .spawn((Name::new("Game Mode Button"), GameModeButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Game Mode Button Text"),
+                                        GameModeButtonText,
+                                        TextBundle::from_section(
+                                            game_mode_label(&mode),
+                                            TextStyle {
+                                                font_size: 40.0,
+                                                color: Color::rgb(0.9, 0.9, 0.9),
+                                                ..Default::default()
+                                            },
+                                        ),
+                                    ));
+                                });
+                            builder
+                                .spawn((Name::new("Biome Button"), BiomeButton, button()))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Name::new("Biome Button Text"),
+                                        BiomeButtonText,
+                                        TextBundle::from_section(
+                                            biome_label(&biome),
+                                            TextStyle {
+                                                font_size: 40.0,
+                                                color: Color::rgb(0.9, 0.9, 0.9),
+                                                ..Default::default()
+                                            },
+                                        ),
+                                    ));
+                                });
                         });
                 });
             builder
@@ -91,6 +350,28 @@ pub(super) fn spawn_start_menu(mut commands: Commands, fonts: Res<FontAssets>) {
         });
 }
 
+fn arrow_button() -> ButtonBundle {
+    let button = button();
+    ButtonBundle {
+        style: Style {
+            width: Val::Px(65.0),
+            ..button.style
+        },
+        ..button
+    }
+}
+
+fn button_text(text: &str) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font_size: 40.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+            ..Default::default()
+        },
+    )
+}
+
 fn title_node(font: Handle<Font>) -> TextBundle {
     TextBundle::from_section(
         GAME_TITLE.to_string(),
@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use bevy_xpbd_3d::prelude::*;
 
 use crate::{
-    despawn_all_recursive, AppState, Cheese, Level, Person, PlayerCamera, SceneAssets, Terrain,
-    TerrainChunk,
+    despawn_all_recursive, AppState, Cheese, CheeseKind, Debris, Lakitu, Level, Obstacle, Person,
+    PlayerCamera, PooledRagdoll, PowerUp, SceneAssets, SelectedCheese, Terrain, TerrainChunk,
+    RACE_START_Z,
 };
 
 mod ui;
@@ -44,7 +45,12 @@ impl Plugin for RaceScenePlugin {
                         despawn_all_recursive::<Cheese>,
                         despawn_all_recursive::<Terrain>,
                         despawn_all_recursive::<TerrainChunk>,
+                        despawn_all_recursive::<Obstacle>,
+                        despawn_all_recursive::<Debris>,
+                        despawn_all_recursive::<PowerUp>,
                         despawn_all_recursive::<Person>,
+                        despawn_all_recursive::<PooledRagdoll>,
+                        despawn_all_recursive::<Lakitu>,
                         despawn_all_recursive::<GameLighting>,
                         despawn_all_recursive::<PlayerCamera>,
                         despawn_all_recursive::<GameOverUI>,
@@ -61,9 +67,13 @@ pub struct GameLighting;
 #[derive(Component)]
 pub struct RaceCountdown(Timer);
 
-const CHEESE_SPAWN_Z: f32 = 50.;
+const CHEESE_SPAWN_Z: f32 = RACE_START_Z;
 
-fn spawn_scene(mut commands: Commands, cheese_scenes: Res<SceneAssets>) {
+fn spawn_scene(
+    mut commands: Commands,
+    cheese_scenes: Res<SceneAssets>,
+    selected_cheese: Res<SelectedCheese>,
+) {
     commands.spawn((
         GameLighting,
         DirectionalLightBundle {
@@ -82,7 +92,12 @@ fn spawn_scene(mut commands: Commands, cheese_scenes: Res<SceneAssets>) {
         RaceCountdown(Timer::from_seconds(3., TimerMode::Once)),
     ));
     let cheese_transform = Transform::from_xyz(0., 50., CHEESE_SPAWN_Z);
-    commands.spawn(Cheese::bundle(cheese_transform, &cheese_scenes));
+    Cheese::spawn(
+        selected_cheese.0,
+        cheese_transform,
+        &cheese_scenes,
+        &mut commands,
+    );
 }
 
 fn begin_countdown(
@@ -97,10 +112,10 @@ fn begin_countdown(
 
 fn ready_cheese(
     spatial_query: SpatialQuery,
-    mut cheese_query: Query<&mut Transform, With<Cheese>>,
+    mut cheese_query: Query<(&mut Transform, &CheeseKind), With<Cheese>>,
     terrain_query: Query<&Transform, (With<TerrainChunk>, Without<Cheese>)>,
 ) {
-    let Ok(mut cheese_transform) = cheese_query.get_single_mut() else {
+    let Ok((mut cheese_transform, kind)) = cheese_query.get_single_mut() else {
         return;
     };
 
@@ -116,7 +131,7 @@ fn ready_cheese(
             return;
         }
         let y = -hit.time_of_impact + 10.;
-        let cheese_spawn_position = Vec3::new(0., y + Cheese::RADIUS * 3., CHEESE_SPAWN_Z);
+        let cheese_spawn_position = Vec3::new(0., y + kind.stats().radius * 3., CHEESE_SPAWN_Z);
         *cheese_transform = Transform::from_translation(cheese_spawn_position)
             .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
     }
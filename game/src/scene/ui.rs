@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{button, AppState, HighScore, Score};
+use crate::{
+    button, format_race_time, AppState, GameMode, GameOverReason, HighScore, PlayTest, RunStats,
+    Score, StyleScore, StyleSource,
+};
 
 use super::RaceCountdown;
 
@@ -63,6 +66,10 @@ pub(super) fn spawn_game_over_ui(
     mut commands: Commands,
     score: Res<Score>,
     high_score: Res<HighScore>,
+    style: Res<StyleScore>,
+    stats: Res<RunStats>,
+    mode: Res<GameMode>,
+    reason: Option<Res<GameOverReason>>,
 ) {
     commands
         .spawn((
@@ -86,7 +93,8 @@ pub(super) fn spawn_game_over_ui(
                     NodeBundle {
                         style: Style {
                             width: Val::Px(400.),
-                            height: Val::Px(400.),
+                            min_height: Val::Px(400.),
+                            padding: UiRect::all(Val::Px(16.)),
                             flex_direction: FlexDirection::Column,
                             justify_content: JustifyContent::SpaceEvenly,
                             align_items: AlignItems::Center,
@@ -110,6 +118,32 @@ pub(super) fn spawn_game_over_ui(
                             ..Default::default()
                         },
                     ));
+                    if let Some(reason) = reason {
+                        builder.spawn(TextBundle::from_section(
+                            reason.message(),
+                            TextStyle {
+                                font_size: 20.0,
+                                color: Color::rgb(0.02, 0.02, 0.1),
+                                ..Default::default()
+                            },
+                        ));
+                    }
+                    let stats_style = TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.02, 0.02, 0.1),
+                        ..Default::default()
+                    };
+                    for line in [
+                        // meters per second to kilometers per hour
+                        format!("Top speed: {:.0} km/h", stats.top_speed * 3.6),
+                        format!("Distance: {:.0} m", stats.distance.max(0.)),
+                        format!("Time: {}", format_race_time(stats.time)),
+                        format!("Ragdolls dodged: {}", stats.ragdolls_dodged),
+                        format!("Obstacles hit: {}", stats.obstacles_hit),
+                        format!("Ragdolls flattened: {}", stats.knockdowns),
+                    ] {
+                        builder.spawn(TextBundle::from_section(line, stats_style.clone()));
+                    }
                     builder.spawn(TextBundle::from_section(
                         "Your score is:",
                         TextStyle {
@@ -126,7 +160,34 @@ pub(super) fn spawn_game_over_ui(
                             ..Default::default()
                         },
                     ));
-                    if score.0 > high_score.0 {
+                    // where the score came from, as long as there were any style points
+                    if style.total > 0. {
+                        let breakdown_style = TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(0.02, 0.02, 0.1),
+                            ..Default::default()
+                        };
+                        builder.spawn(TextBundle::from_section(
+                            format!("{}: {:.0}", mode.score_label(), score.0 - style.total),
+                            breakdown_style.clone(),
+                        ));
+                        for source in StyleSource::ALL {
+                            let tally = style.tally(source);
+                            if tally.count == 0 {
+                                continue;
+                            }
+                            builder.spawn(TextBundle::from_section(
+                                format!("{} x{}: {:.0}", source.label(), tally.count, tally.points),
+                                breakdown_style.clone(),
+                            ));
+                        }
+                        builder.spawn(TextBundle::from_section(
+                            format!("Best combo: x{}", style.best_combo),
+                            breakdown_style,
+                        ));
+                    }
+                    let high_score = high_score.get(*mode);
+                    if score.0 > high_score {
                         builder.spawn(TextBundle::from_section(
                             "New high score!",
                             TextStyle {
@@ -137,7 +198,7 @@ pub(super) fn spawn_game_over_ui(
                         ));
                     }
                     builder.spawn(TextBundle::from_section(
-                        "Previous high score:",
+                        format!("Previous {} high score:", mode.label()),
                         TextStyle {
                             font_size: 24.0,
                             color: Color::rgb(0.02, 0.02, 0.1),
@@ -145,7 +206,7 @@ pub(super) fn spawn_game_over_ui(
                         },
                     ));
                     builder.spawn(TextBundle::from_section(
-                        format!("{:.0}", high_score.0),
+                        format!("{:.0}", high_score),
                         TextStyle {
                             font_size: 24.0,
                             color: Color::rgb(0.02, 0.02, 0.1),
@@ -204,11 +265,16 @@ pub(super) fn handle_replay_action(
 
 pub(super) fn handle_quit_action(
     interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+    play_test: Option<Res<PlayTest>>,
     mut state: ResMut<NextState<AppState>>,
 ) {
     for interaction in interaction_query.iter() {
         if let Interaction::Pressed = interaction {
-            state.set(AppState::Menu);
+            if play_test.is_some() {
+                state.set(AppState::Editor);
+            } else {
+                state.set(AppState::Menu);
+            }
         }
     }
 }